@@ -0,0 +1,67 @@
+use circularbuffer::CircularBuffer;
+
+fn main() {
+    // Création d'un buffer circulaire de taille 5
+    let mut buffer = CircularBuffer::new(5);
+
+    // Ajout d'éléments au buffer
+    buffer.push(10);
+    buffer.push(20);
+    buffer.push(30);
+    println!("Buffer: {}", buffer); // Affiche : Buffer: [10, 20, 30]
+
+    buffer.push(40);
+    buffer.push(50);
+    println!("Buffer: {}", buffer); // Affiche : Buffer: [10, 20, 30, 40, 50]
+
+    buffer.push(60); // Écrase le plus ancien élément (10)
+    println!("Buffer: {}", buffer); // Affiche : Buffer: [20, 30, 40, 50, 60]
+
+    // Retrait d'éléments
+    let popped = buffer.pop();
+    println!("Popped: {:?}", popped); // Affiche : Popped: Some(20)
+    println!("Buffer: {}", buffer); // Affiche : Buffer: [30, 40, 50, 60]
+
+    buffer.push(70);
+    println!("Buffer: {}", buffer); // Affiche : Buffer: [30, 40, 50, 60, 70]
+
+    // Utilisation de peek
+    if let Some(peeked) = buffer.peek() {
+        println!("Peeked: {:?}", peeked); // Affiche : Peeked: 30
+    }
+
+    // Vérification de contains
+    println!("Contains 30: {}", buffer.contains(&30)); // Affiche : Contains 30: true
+    println!("Contains 100: {}", buffer.contains(&100)); // Affiche : Contains 100: false
+
+    // Affichage de la taille et capacité
+    println!("Taille du buffer: {}", buffer.len()); // Affiche : Taille du buffer: 5
+    println!("Capacité du buffer: {}", buffer.capacity()); // Affiche : Capacité du buffer: 5
+
+    // Vider le buffer
+    buffer.clear();
+    println!("Buffer: {}", buffer); // Affiche : Buffer: []
+
+    // Redimensionnement du buffer
+    match buffer.resize(7) {
+        Ok(()) => {
+            println!("Redimensionnement réussi à 7...");
+            buffer.push(80);
+            buffer.push(90);
+            println!("Buffer: {}", buffer); // Affiche : Buffer: [80, 90]
+        }
+        Err(err) => println!("Erreur de redimensionnement: {}", err),
+    }
+
+    // Traverser le buffer
+    for val in buffer.iter() {
+        println!("Iterated: {:?}", val);
+    }
+
+    // Affichage détaillé, avec la position de "tail" et "head"
+    println!("{:?}", buffer); // Affiche : CircularBuffer { items: [80, 90], tail: 0, head: 2, capacity: 7 }
+
+    // Réduire la capacité à la taille utilisée
+    buffer.shrink_to_fit();
+    println!("Capacité après shrink_to_fit: {}", buffer.capacity());
+}
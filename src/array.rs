@@ -0,0 +1,120 @@
+//! Variante du buffer circulaire à capacité fixée à la compilation.
+
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+
+use crate::CircularBuffer;
+
+/// Buffer circulaire de capacité `N`, stocké directement dans la structure.
+///
+/// Aucune allocation sur le tas n'est effectuée : la capacité est connue à la
+/// compilation, ce qui permet de l'utiliser sur la pile ou dans une `static`.
+/// C'est un [`CircularBuffer`] sur un tableau, dont toute l'interface est
+/// accessible par `Deref` ; seul son constructeur est `const`.
+pub struct ArrayCircularBuffer<T, const N: usize>(CircularBuffer<T, [MaybeUninit<T>; N]>);
+
+impl<T, const N: usize> ArrayCircularBuffer<T, N> {
+    /// Création d'un nouveau buffer circulaire vide
+    ///
+    /// Une capacité `N` nulle est refusée à la compilation.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "La taille du buffer doit être positive.") };
+        Self(CircularBuffer::new_with_storage(
+            [const { MaybeUninit::uninit() }; N],
+        ))
+    }
+
+    /// Retourne le [`CircularBuffer`] sur tableau
+    pub fn into_inner(self) -> CircularBuffer<T, [MaybeUninit<T>; N]> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Default for ArrayCircularBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayCircularBuffer<T, N> {
+    type Target = CircularBuffer<T, [MaybeUninit<T>; N]>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayCircularBuffer<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayCircularBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Display for ArrayCircularBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop_fifo() {
+        let mut buffer = ArrayCircularBuffer::<i32, 3>::new();
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(4), Some(1));
+        assert_eq!(buffer.peek(), Some(&2));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(buffer.contains(&4));
+        assert_eq!(format!("{}", buffer), "[2, 3, 4]");
+        assert_eq!(buffer.try_push(5), Err(5));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), Some(4));
+        assert_eq!(buffer.pop(), None);
+    }
+
+    #[test]
+    fn test_elements_liberes() {
+        let jeton = Rc::new(());
+        {
+            let mut buffer = ArrayCircularBuffer::<_, 2>::new();
+            buffer.push(Rc::clone(&jeton));
+            buffer.push(Rc::clone(&jeton));
+            buffer.push(Rc::clone(&jeton)); // écrase le premier
+            assert_eq!(Rc::strong_count(&jeton), 3);
+            buffer.clear();
+            assert_eq!(Rc::strong_count(&jeton), 1);
+            buffer.push(Rc::clone(&jeton));
+        }
+        assert_eq!(Rc::strong_count(&jeton), 1);
+    }
+
+    #[test]
+    fn test_interface_complete() {
+        static mut _STATIQUE: ArrayCircularBuffer<u8, 4> = ArrayCircularBuffer::new();
+        let mut buffer = ArrayCircularBuffer::<i32, 4>::new();
+        buffer.extend([4, 1, 3]);
+        buffer.push_front(2);
+        buffer.sort();
+        assert_eq!(*buffer, [1, 2, 3, 4]);
+        assert_eq!(
+            buffer.resize(8),
+            Err(crate::CircularBufferError::FixedCapacity)
+        );
+        assert_eq!(buffer.drain().rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    }
+}
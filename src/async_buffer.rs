@@ -0,0 +1,606 @@
+//! Buffer partagé entre tâches asynchrones (fonctionnalité `async`).
+//!
+//! Avec la fonctionnalité `futures`, les moitiés obtenues par
+//! [`AsyncCircularBuffer::split`] implémentent `Sink` et `Stream` et se
+//! combinent avec `SinkExt` / `StreamExt`.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{CircularBuffer, CircularBufferError, OverflowPolicy};
+
+struct State<T> {
+    buffer: CircularBuffer<T>,
+    // Tâches attendant respectivement une place et un élément
+    push_wakers: Vec<Waker>,
+    pop_wakers: Vec<Waker>,
+    // L'une des moitiés obtenues par `split` a été abandonnée ou fermée
+    closed: bool,
+}
+
+/// Buffer circulaire dont [`push`](Self::push) et [`pop`](Self::pop)
+/// renvoient des futures.
+///
+/// `push(item).await` se termine lorsqu'une place est disponible et
+/// `pop().await` lorsqu'un élément arrive. Les tâches en attente sont
+/// réveillées par leur `Waker`, sans interrogation périodique ; le buffer ne
+/// dépend d'aucun exécuteur. Il est partagé par référence, par exemple dans
+/// un `Arc`.
+pub struct AsyncCircularBuffer<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> AsyncCircularBuffer<T> {
+    /// Création d'un buffer vide de capacité `capacity`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(State {
+                buffer: CircularBuffer::with_policy(capacity, OverflowPolicy::Reject),
+                push_wakers: Vec::new(),
+                pop_wakers: Vec::new(),
+                closed: false,
+            }),
+        }
+    }
+
+    /// Ajout d'un élément ; la future se termine lorsque l'élément a trouvé
+    /// une place
+    pub fn push(&self, item: T) -> PushFuture<'_, T> {
+        PushFuture {
+            buffer: self,
+            item: Some(item),
+        }
+    }
+
+    /// Retrait de l'élément le plus ancien ; la future se termine lorsqu'un
+    /// élément est disponible
+    pub fn pop(&self) -> PopFuture<'_, T> {
+        PopFuture { buffer: self }
+    }
+
+    /// Sépare le buffer en une moitié qui ajoute et une moitié qui retire
+    ///
+    /// Lorsque le producteur est abandonné, [`AsyncConsumer::pop`] rend `None`
+    /// et le `Stream` se termine une fois les éléments restants retirés ;
+    /// lorsque le consommateur est abandonné, les ajouts du producteur sont
+    /// refusés.
+    pub fn split(self) -> (AsyncProducer<T>, AsyncConsumer<T>) {
+        let buffer = Arc::new(self);
+        (
+            AsyncProducer {
+                buffer: Arc::clone(&buffer),
+            },
+            AsyncConsumer { buffer },
+        )
+    }
+
+    /// Ajout d'un élément sans attendre : si le buffer est plein, l'élément
+    /// est rendu dans `Err`
+    pub fn try_push(&self, item: T) -> Result<(), T> {
+        let mut state = self.lock();
+        state.buffer.try_push(item)?;
+        wake_all(&mut state.pop_wakers);
+        Ok(())
+    }
+
+    /// Retrait de l'élément le plus ancien sans attendre
+    pub fn try_pop(&self) -> Option<T> {
+        let mut state = self.lock();
+        let item = state.buffer.pop()?;
+        wake_all(&mut state.push_wakers);
+        Some(item)
+    }
+
+    /// Retourne la taille actuelle du buffer
+    pub fn len(&self) -> usize {
+        self.lock().buffer.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.lock().buffer.is_empty()
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.lock().buffer.is_full()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.lock().buffer.capacity()
+    }
+
+    /// Vérifie si l'une des moitiés obtenues par [`split`](Self::split) a été
+    /// fermée ou abandonnée
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        wake_all(&mut state.pop_wakers);
+        wake_all(&mut state.push_wakers);
+    }
+}
+
+// Toutes les tâches en attente sont réveillées : une future abandonnée ne
+// peut donc pas retenir à elle seule le réveil destiné aux autres.
+fn wake_all(wakers: &mut Vec<Waker>) {
+    for waker in wakers.drain(..) {
+        waker.wake();
+    }
+}
+
+fn register(wakers: &mut Vec<Waker>, waker: &Waker) {
+    if !wakers.iter().any(|w| w.will_wake(waker)) {
+        wakers.push(waker.clone());
+    }
+}
+
+/// Future renvoyée par [`AsyncCircularBuffer::push`].
+#[must_use = "les futures ne font rien tant qu'elles ne sont pas attendues"]
+pub struct PushFuture<'a, T> {
+    buffer: &'a AsyncCircularBuffer<T>,
+    item: Option<T>,
+}
+
+// L'élément n'est jamais épinglé : il est seulement déplacé dans le buffer.
+impl<T> Unpin for PushFuture<'_, T> {}
+
+impl<T> Future for PushFuture<'_, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let Some(item) = self.item.take() else {
+            return Poll::Ready(());
+        };
+        let mut state = self.buffer.lock();
+        match state.buffer.try_push(item) {
+            Ok(()) => {
+                wake_all(&mut state.pop_wakers);
+                Poll::Ready(())
+            }
+            Err(item) => {
+                register(&mut state.push_wakers, cx.waker());
+                drop(state);
+                self.item = Some(item);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future renvoyée par [`AsyncCircularBuffer::pop`].
+#[must_use = "les futures ne font rien tant qu'elles ne sont pas attendues"]
+pub struct PopFuture<'a, T> {
+    buffer: &'a AsyncCircularBuffer<T>,
+}
+
+impl<T> Future for PopFuture<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.buffer.lock();
+        match state.buffer.pop() {
+            Some(item) => {
+                wake_all(&mut state.push_wakers);
+                Poll::Ready(item)
+            }
+            None => {
+                register(&mut state.pop_wakers, cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future renvoyée par [`AsyncProducer::push`].
+#[must_use = "les futures ne font rien tant qu'elles ne sont pas attendues"]
+pub struct SendFuture<'a, T> {
+    buffer: &'a AsyncCircularBuffer<T>,
+    item: Option<T>,
+}
+
+// L'élément n'est jamais épinglé : il est seulement déplacé dans le buffer.
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), T>> {
+        let Some(item) = self.item.take() else {
+            return Poll::Ready(Ok(()));
+        };
+        let mut state = self.buffer.lock();
+        if state.closed {
+            return Poll::Ready(Err(item));
+        }
+        match state.buffer.try_push(item) {
+            Ok(()) => {
+                wake_all(&mut state.pop_wakers);
+                Poll::Ready(Ok(()))
+            }
+            Err(item) => {
+                register(&mut state.push_wakers, cx.waker());
+                drop(state);
+                self.item = Some(item);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future renvoyée par [`AsyncConsumer::pop`].
+#[must_use = "les futures ne font rien tant qu'elles ne sont pas attendues"]
+pub struct RecvFuture<'a, T> {
+    buffer: &'a AsyncCircularBuffer<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.buffer.lock();
+        match state.buffer.pop() {
+            Some(item) => {
+                wake_all(&mut state.push_wakers);
+                Poll::Ready(Some(item))
+            }
+            None if state.closed => Poll::Ready(None),
+            None => {
+                register(&mut state.pop_wakers, cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Moitié d'un [`AsyncCircularBuffer`] qui ajoute des éléments, créée par
+/// [`AsyncCircularBuffer::split`].
+///
+/// Avec la fonctionnalité `futures`, elle implémente `Sink<T>`.
+pub struct AsyncProducer<T> {
+    buffer: Arc<AsyncCircularBuffer<T>>,
+}
+
+impl<T> AsyncProducer<T> {
+    /// Ajout d'un élément ; la future se termine lorsque l'élément a trouvé
+    /// une place, ou le rend dans `Err` si le consommateur est abandonné
+    pub fn push(&self, item: T) -> SendFuture<'_, T> {
+        SendFuture {
+            buffer: &self.buffer,
+            item: Some(item),
+        }
+    }
+
+    /// Ajout d'un élément sans attendre : l'élément est rendu dans `Err` si
+    /// le buffer est plein ou si le consommateur est abandonné
+    pub fn try_push(&self, item: T) -> Result<(), T> {
+        if self.is_closed() {
+            return Err(item);
+        }
+        self.buffer.try_push(item)
+    }
+
+    /// Retourne la taille actuelle du buffer
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Ferme le buffer : le consommateur retire encore les éléments en attente,
+    /// puis [`AsyncConsumer::try_recv`] renvoie
+    /// [`CircularBufferError::Closed`] et le `Stream` se termine
+    ///
+    /// Équivaut à abandonner le producteur. Cette méthode masque
+    /// `SinkExt::close`, qui reste accessible sous cette forme complète.
+    pub fn close(self) {
+        drop(self);
+    }
+
+    /// Vérifie si le consommateur a été abandonné
+    pub fn is_closed(&self) -> bool {
+        self.buffer.is_closed()
+    }
+}
+
+impl<T> Drop for AsyncProducer<T> {
+    fn drop(&mut self) {
+        self.buffer.close();
+    }
+}
+
+/// Moitié d'un [`AsyncCircularBuffer`] qui retire des éléments, créée par
+/// [`AsyncCircularBuffer::split`].
+///
+/// Avec la fonctionnalité `futures`, elle implémente `Stream<Item = T>`.
+pub struct AsyncConsumer<T> {
+    buffer: Arc<AsyncCircularBuffer<T>>,
+}
+
+impl<T> AsyncConsumer<T> {
+    /// Retrait de l'élément le plus ancien ; la future se termine lorsqu'un
+    /// élément est disponible, ou avec `None` si le producteur est fermé et
+    /// le buffer vide
+    pub fn pop(&self) -> RecvFuture<'_, T> {
+        RecvFuture {
+            buffer: &self.buffer,
+        }
+    }
+
+    /// Voir [`AsyncCircularBuffer::try_pop`]
+    pub fn try_pop(&self) -> Option<T> {
+        self.buffer.try_pop()
+    }
+
+    /// Retrait sans attendre, en distinguant un buffer momentanément vide
+    /// ([`CircularBufferError::Empty`]) d'un buffer vide dont le producteur a
+    /// été fermé ([`CircularBufferError::Closed`])
+    pub fn try_recv(&self) -> Result<T, CircularBufferError> {
+        let mut state = self.buffer.lock();
+        match state.buffer.pop() {
+            Some(item) => {
+                wake_all(&mut state.push_wakers);
+                Ok(item)
+            }
+            None if state.closed => Err(CircularBufferError::Closed),
+            None => Err(CircularBufferError::Empty),
+        }
+    }
+
+    /// Vérifie si le producteur a été fermé ou abandonné ; des éléments
+    /// peuvent encore être en attente
+    pub fn is_closed(&self) -> bool {
+        self.buffer.is_closed()
+    }
+
+    /// Retourne la taille actuelle du buffer
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+}
+
+impl<T> Drop for AsyncConsumer<T> {
+    fn drop(&mut self) {
+        self.buffer.close();
+    }
+}
+
+/// Un élément n'est accepté par `start_send` qu'après un `poll_ready` prêt ;
+/// sinon l'envoi échoue avec [`CircularBufferError::Full`]. Une fois le
+/// consommateur abandonné, l'envoi échoue avec [`CircularBufferError::Closed`].
+/// Fermer le `Sink` termine le `Stream` du consommateur.
+#[cfg(feature = "futures")]
+impl<T> futures_sink::Sink<T> for AsyncProducer<T> {
+    type Error = CircularBufferError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let mut state = self.buffer.lock();
+        if state.closed {
+            Poll::Ready(Err(CircularBufferError::Closed))
+        } else if state.buffer.is_full() {
+            register(&mut state.push_wakers, cx.waker());
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        if self.is_closed() {
+            return Err(CircularBufferError::Closed);
+        }
+        self.buffer
+            .try_push(item)
+            .map_err(|_| CircularBufferError::Full)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.buffer.close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> futures_core::Stream for AsyncConsumer<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.buffer.lock();
+        if let Some(item) = state.buffer.pop() {
+            wake_all(&mut state.push_wakers);
+            Poll::Ready(Some(item))
+        } else if state.closed {
+            Poll::Ready(None)
+        } else {
+            register(&mut state.pop_wakers, cx.waker());
+            Poll::Pending
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len(), None)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    // Exécuteur minimal : endort le thread jusqu'au réveil de la future
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = core::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_push_pop_entre_taches() {
+        let buffer = Arc::new(AsyncCircularBuffer::new(2));
+        let producteur = {
+            let buffer = Arc::clone(&buffer);
+            thread::spawn(move || {
+                block_on(async {
+                    for i in 0..100 {
+                        buffer.push(i).await;
+                    }
+                })
+            })
+        };
+        let recus = block_on(async {
+            let mut recus = Vec::new();
+            for _ in 0..100 {
+                recus.push(buffer.pop().await);
+            }
+            recus
+        });
+        producteur.join().unwrap();
+        assert_eq!(recus, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_attente_enregistree() {
+        let buffer = AsyncCircularBuffer::new(1);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut pop = buffer.pop();
+        assert!(Pin::new(&mut pop).poll(&mut cx).is_pending());
+        assert_eq!(buffer.try_push(1), Ok(()));
+        assert_eq!(Pin::new(&mut pop).poll(&mut cx), Poll::Ready(1));
+
+        buffer.try_push(2).unwrap();
+        let mut push = buffer.push(3);
+        assert!(Pin::new(&mut push).poll(&mut cx).is_pending());
+        assert_eq!(buffer.try_pop(), Some(2));
+        assert!(Pin::new(&mut push).poll(&mut cx).is_ready());
+        assert_eq!(buffer.try_pop(), Some(3));
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_stream_et_sink() {
+        use futures::{SinkExt, StreamExt};
+
+        let (mut producteur, consommateur) = AsyncCircularBuffer::new(2).split();
+        let envoi = thread::spawn(move || {
+            block_on(async {
+                let mut source = futures::stream::iter(0..50).map(Ok);
+                producteur.send_all(&mut source).await.unwrap();
+                SinkExt::close(&mut producteur).await.unwrap();
+            })
+        });
+        let recus: Vec<i32> = block_on(consommateur.map(|x| x * 2).collect());
+        envoi.join().unwrap();
+        assert_eq!(recus, (0..50).map(|x| x * 2).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_fin_apres_abandon_du_producteur() {
+        use futures::StreamExt;
+
+        let (producteur, consommateur) = AsyncCircularBuffer::new(4).split();
+        producteur.try_push(1).unwrap();
+        producteur.try_push(2).unwrap();
+        drop(producteur);
+        assert_eq!(block_on(consommateur.collect::<Vec<_>>()), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_fermeture() {
+        let (producteur, consommateur) = AsyncCircularBuffer::new(4).split();
+        assert_eq!(consommateur.try_recv(), Err(CircularBufferError::Empty));
+        producteur.try_push(1).unwrap();
+        assert!(!producteur.is_closed());
+        producteur.close();
+        assert!(consommateur.is_closed());
+        assert_eq!(consommateur.try_recv(), Ok(1));
+        assert_eq!(consommateur.try_recv(), Err(CircularBufferError::Closed));
+    }
+
+    #[test]
+    fn test_pop_termine_apres_fermeture() {
+        let (producteur, consommateur) = AsyncCircularBuffer::new(4).split();
+        let attente = thread::spawn(move || {
+            block_on(async {
+                let mut recus = Vec::new();
+                while let Some(item) = consommateur.pop().await {
+                    recus.push(item);
+                }
+                recus
+            })
+        });
+        block_on(async {
+            producteur.push(1).await.unwrap();
+            producteur.push(2).await.unwrap();
+        });
+        drop(producteur);
+        assert_eq!(attente.join().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn test_abandon_du_consommateur() {
+        let (producteur, consommateur) = AsyncCircularBuffer::new(1).split();
+        producteur.try_push(1).unwrap();
+        let envoi = thread::spawn(move || {
+            let rendu = block_on(producteur.push(2));
+            (rendu, producteur.is_closed(), producteur.try_push(3))
+        });
+        thread::sleep(std::time::Duration::from_millis(20));
+        drop(consommateur);
+        assert_eq!(envoi.join().unwrap(), (Err(2), true, Err(3)));
+    }
+}
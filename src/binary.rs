@@ -0,0 +1,202 @@
+//! Sauvegarde binaire compacte d'un buffer vers un `Write` et relecture depuis
+//! un `Read` (fonctionnalité `std`).
+//!
+//! Format, entiers en petit-boutiste :
+//!
+//! | Champ     | Taille    | Contenu                                  |
+//! |-----------|-----------|------------------------------------------|
+//! | signature | 4 octets  | `CBUF`                                   |
+//! | version   | 1 octet   | `1`                                      |
+//! | capacité  | 8 octets  | `u64`                                    |
+//! | nombre    | 8 octets  | `u64`                                    |
+//! | éléments  | variable  | du plus ancien au plus récent            |
+
+use std::io::{self, Read, Write};
+
+use crate::{CircularBuffer, CircularBufferError};
+
+const MAGIC: &[u8; 4] = b"CBUF";
+const VERSION: u8 = 1;
+
+/// Élément pouvant être écrit dans une sauvegarde binaire.
+///
+/// Implémenté pour les types numériques (taille fixe, petit-boutiste), `bool`,
+/// `String` et `Vec<u8>` (précédés de leur longueur en `u64`).
+pub trait BinaryElement: Sized {
+    /// Écrit l'élément dans `w`
+    fn write_to(&self, w: &mut dyn Write) -> io::Result<()>;
+
+    /// Relit un élément écrit par [`write_to`](Self::write_to)
+    fn read_from(r: &mut dyn Read) -> io::Result<Self>;
+}
+
+macro_rules! impl_binary_number {
+    ($($t:ty),*) => {$(
+        impl BinaryElement for $t {
+            fn write_to(&self, w: &mut dyn Write) -> io::Result<()> {
+                w.write_all(&self.to_le_bytes())
+            }
+
+            fn read_from(r: &mut dyn Read) -> io::Result<Self> {
+                let mut bytes = [0; core::mem::size_of::<$t>()];
+                r.read_exact(&mut bytes)?;
+                Ok(<$t>::from_le_bytes(bytes))
+            }
+        }
+    )*};
+}
+
+impl_binary_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl BinaryElement for bool {
+    fn write_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        u8::from(*self).write_to(w)
+    }
+
+    fn read_from(r: &mut dyn Read) -> io::Result<Self> {
+        match u8::read_from(r)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("Booléen invalide.")),
+        }
+    }
+}
+
+impl BinaryElement for Vec<u8> {
+    fn write_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        (self.len() as u64).write_to(w)?;
+        w.write_all(self)
+    }
+
+    fn read_from(r: &mut dyn Read) -> io::Result<Self> {
+        let len = read_len(r)?;
+        let mut bytes = Vec::new();
+        r.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(bytes)
+    }
+}
+
+impl BinaryElement for String {
+    fn write_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        (self.len() as u64).write_to(w)?;
+        w.write_all(self.as_bytes())
+    }
+
+    fn read_from(r: &mut dyn Read) -> io::Result<Self> {
+        String::from_utf8(Vec::read_from(r)?).map_err(|_| invalid_data("Chaîne UTF-8 invalide."))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Lit une longueur écrite en `u64`
+fn read_len(r: &mut dyn Read) -> io::Result<usize> {
+    usize::try_from(u64::read_from(r)?).map_err(|_| invalid_data("Longueur trop grande."))
+}
+
+impl<T: BinaryElement> CircularBuffer<T> {
+    /// Écrit une sauvegarde binaire versionnée du buffer : capacité, nombre
+    /// d'éléments puis les éléments du plus ancien au plus récent
+    pub fn save_to(&self, mut w: impl Write) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        VERSION.write_to(&mut w)?;
+        (self.capacity() as u64).write_to(&mut w)?;
+        (self.len() as u64).write_to(&mut w)?;
+        for item in self.iter() {
+            item.write_to(&mut w)?;
+        }
+        w.flush()
+    }
+
+    /// Relit une sauvegarde écrite par [`save_to`](Self::save_to)
+    ///
+    /// Le buffer obtenu utilise la politique par défaut. Une capacité qui ne
+    /// peut pas être allouée renvoie une erreur `InvalidData`.
+    pub fn load_from(mut r: impl Read) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("Signature de sauvegarde invalide."));
+        }
+        let version = u8::read_from(&mut r)?;
+        if version != VERSION {
+            return Err(invalid_data("Version de sauvegarde non prise en charge."));
+        }
+        let capacity = read_len(&mut r)?;
+        let count = read_len(&mut r)?;
+        if capacity == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                CircularBufferError::ZeroCapacity,
+            ));
+        }
+        if count > capacity {
+            return Err(invalid_data("Plus d'éléments que la capacité du buffer."));
+        }
+
+        let mut buffer = Self::try_allocate(capacity)
+            .ok_or_else(|| invalid_data("Capacité de sauvegarde trop grande."))?;
+        for _ in 0..count {
+            buffer.push(T::read_from(&mut r)?);
+        }
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CircularBuffer;
+
+    #[test]
+    fn test_aller_retour() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend([1u32, 2, 3, 4]);
+        let mut sauvegarde = Vec::new();
+        buffer.save_to(&mut sauvegarde).unwrap();
+        assert_eq!(sauvegarde.len(), 4 + 1 + 8 + 8 + 3 * 4);
+
+        let relu = CircularBuffer::<u32>::load_from(&sauvegarde[..]).unwrap();
+        assert_eq!(relu.capacity(), 3);
+        assert_eq!(relu, [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_chaines() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.push(String::from("démarrage"));
+        buffer.push(String::new());
+        let mut sauvegarde = Vec::new();
+        buffer.save_to(&mut sauvegarde).unwrap();
+        let relu = CircularBuffer::<String>::load_from(sauvegarde.as_slice()).unwrap();
+        assert_eq!(relu, buffer);
+    }
+
+    #[test]
+    fn test_sauvegarde_invalide() {
+        let mut buffer = CircularBuffer::new(2);
+        buffer.push(7u8);
+        let mut sauvegarde = Vec::new();
+        buffer.save_to(&mut sauvegarde).unwrap();
+
+        let tronquee = &sauvegarde[..sauvegarde.len() - 1];
+        assert!(CircularBuffer::<u8>::load_from(tronquee).is_err());
+        let mut mauvaise_version = sauvegarde.clone();
+        mauvaise_version[4] = 9;
+        assert!(CircularBuffer::<u8>::load_from(&mauvaise_version[..]).is_err());
+        assert!(CircularBuffer::<u8>::load_from(&b"XXXX"[..]).is_err());
+    }
+
+    #[test]
+    fn test_capacite_impossible_a_allouer() {
+        let mut sauvegarde = b"CBUF\x01".to_vec();
+        sauvegarde.extend_from_slice(&(1u64 << 60).to_le_bytes());
+        sauvegarde.extend_from_slice(&0u64.to_le_bytes());
+        let erreur = CircularBuffer::<u32>::load_from(&sauvegarde[..]).unwrap_err();
+        assert_eq!(erreur.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,238 @@
+//! Buffer partagé entre threads dont les opérations attendent de la place ou
+//! des éléments (fonctionnalité `std`).
+
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+/// Buffer circulaire protégé par un verrou, dont [`push`](Self::push) attend
+/// qu'une place se libère et [`pop`](Self::pop) qu'un élément arrive.
+///
+/// L'attente endort le thread sur une `Condvar` au lieu de boucler sur
+/// `is_full()`. Le buffer est partagé par référence, par exemple dans un `Arc`.
+pub struct BlockingCircularBuffer<T> {
+    buffer: Mutex<CircularBuffer<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T> BlockingCircularBuffer<T> {
+    /// Création d'un buffer vide de capacité `capacity`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: Mutex::new(CircularBuffer::with_policy(
+                capacity,
+                OverflowPolicy::Reject,
+            )),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    /// Ajout d'un élément, en attendant une place si le buffer est plein
+    pub fn push(&self, item: T) {
+        let mut buffer = self.lock();
+        while buffer.is_full() {
+            buffer = self
+                .not_full
+                .wait(buffer)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        self.push_locked(buffer, item);
+    }
+
+    /// Ajout d'un élément en attendant au plus `timeout` ; à l'échéance,
+    /// l'élément est rendu dans `Err`
+    pub fn push_timeout(&self, item: T, timeout: Duration) -> Result<(), T> {
+        let deadline = Instant::now() + timeout;
+        let mut buffer = self.lock();
+        while buffer.is_full() {
+            let Some(remaining) = deadline.checked_duration_since(Instant::now()) else {
+                return Err(item);
+            };
+            buffer = self
+                .not_full
+                .wait_timeout(buffer, remaining)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        self.push_locked(buffer, item);
+        Ok(())
+    }
+
+    /// Ajout d'un élément sans attendre : si le buffer est plein, l'élément
+    /// est rendu dans `Err`
+    pub fn try_push(&self, item: T) -> Result<(), T> {
+        let buffer = self.lock();
+        if buffer.is_full() {
+            return Err(item);
+        }
+        self.push_locked(buffer, item);
+        Ok(())
+    }
+
+    /// Retrait de l'élément le plus ancien, en attendant qu'un élément arrive
+    /// si le buffer est vide
+    pub fn pop(&self) -> T {
+        let mut buffer = self.lock();
+        loop {
+            if let Some(item) = buffer.pop() {
+                self.not_full.notify_one();
+                return item;
+            }
+            buffer = self
+                .not_empty
+                .wait(buffer)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Retrait de l'élément le plus ancien en attendant au plus `timeout`
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut buffer = self.lock();
+        loop {
+            if let Some(item) = buffer.pop() {
+                self.not_full.notify_one();
+                return Some(item);
+            }
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            buffer = self
+                .not_empty
+                .wait_timeout(buffer, remaining)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /// Retrait de l'élément le plus ancien sans attendre
+    pub fn try_pop(&self) -> Option<T> {
+        let item = self.lock().pop()?;
+        self.not_full.notify_one();
+        Some(item)
+    }
+
+    /// Retrait des `n` plus anciens éléments, ou de tous s'il y en a moins,
+    /// sous un seul verrouillage ; attend qu'au moins un élément arrive si le
+    /// buffer est vide et que `n` est positif
+    pub fn pop_up_to(&self, n: usize) -> Vec<T> {
+        let mut buffer = self.lock();
+        while n > 0 && buffer.is_empty() {
+            buffer = self
+                .not_empty
+                .wait(buffer)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        let items = buffer.pop_up_to(n);
+        self.not_full.notify_all();
+        items
+    }
+
+    /// Retrait des `n` plus anciens éléments d'un bloc sans attendre : si le
+    /// buffer en contient moins, rien n'est retiré
+    pub fn try_pop_exact(&self, n: usize) -> Option<Vec<T>> {
+        let items = self.lock().pop_exact(n)?;
+        self.not_full.notify_all();
+        Some(items)
+    }
+
+    /// Retourne la taille actuelle du buffer
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.lock().is_full()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity()
+    }
+
+    /// Rend le buffer circulaire sous-jacent
+    pub fn into_inner(self) -> CircularBuffer<T> {
+        self.buffer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Un thread qui a paniqué en tenant le verrou laisse le buffer cohérent :
+    // aucune opération ne panique au milieu d'une modification.
+    fn lock(&self) -> MutexGuard<'_, CircularBuffer<T>> {
+        self.buffer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push_locked(&self, mut buffer: MutexGuard<'_, CircularBuffer<T>>, item: T) {
+        let rejected = buffer.push(item);
+        debug_assert!(rejected.is_none());
+        drop(buffer);
+        self.not_empty.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_attente_entre_threads() {
+        let buffer = Arc::new(BlockingCircularBuffer::new(2));
+        let producteur = {
+            let buffer = Arc::clone(&buffer);
+            thread::spawn(move || {
+                for i in 0..100 {
+                    buffer.push(i);
+                }
+            })
+        };
+        let recus: Vec<i32> = (0..100).map(|_| buffer.pop()).collect();
+        producteur.join().unwrap();
+        assert_eq!(recus, (0..100).collect::<Vec<_>>());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_delais_expires() {
+        let buffer = BlockingCircularBuffer::new(1);
+        assert_eq!(buffer.pop_timeout(Duration::from_millis(10)), None);
+        assert_eq!(buffer.push_timeout(1, Duration::from_millis(10)), Ok(()));
+        assert_eq!(buffer.push_timeout(2, Duration::from_millis(10)), Err(2));
+        assert_eq!(buffer.try_push(3), Err(3));
+        assert_eq!(buffer.pop_timeout(Duration::from_millis(10)), Some(1));
+        assert_eq!(buffer.try_pop(), None);
+    }
+
+    #[test]
+    fn test_retrait_par_lots() {
+        let buffer = Arc::new(BlockingCircularBuffer::new(4));
+        let producteur = {
+            let buffer = Arc::clone(&buffer);
+            thread::spawn(move || {
+                for i in 0..100 {
+                    buffer.push(i);
+                }
+            })
+        };
+        let mut recus = Vec::new();
+        while recus.len() < 100 {
+            let lot = buffer.pop_up_to(3);
+            assert!((1..=3).contains(&lot.len()));
+            recus.extend(lot);
+        }
+        producteur.join().unwrap();
+        assert_eq!(recus, (0..100).collect::<Vec<_>>());
+        assert_eq!(buffer.try_pop_exact(1), None);
+    }
+}
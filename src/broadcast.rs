@@ -0,0 +1,167 @@
+//! Diffusion d'un producteur vers plusieurs lecteurs (fonctionnalité `std`).
+//!
+//! Chaque [`Reader`] possède son propre curseur et voit tous les éléments
+//! ajoutés après son abonnement. Le buffer écrase toujours le plus ancien
+//! élément : un lecteur trop lent est prévenu par
+//! [`CircularBufferError::Lagged`] du nombre d'éléments manqués.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{CircularBuffer, CircularBufferError};
+
+struct Shared<T> {
+    buffer: CircularBuffer<T>,
+    // Nombre total d'éléments ajoutés depuis la création
+    written: u64,
+}
+
+impl<T> Shared<T> {
+    // Position du plus ancien élément encore présent
+    fn oldest(&self) -> u64 {
+        self.written - self.buffer.len() as u64
+    }
+}
+
+/// Buffer circulaire dont chaque élément est lu par tous les abonnés.
+pub struct BroadcastBuffer<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> BroadcastBuffer<T> {
+    /// Création d'un buffer vide de capacité `capacity`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(Mutex::new(Shared {
+                buffer: CircularBuffer::new(capacity),
+                written: 0,
+            })),
+        }
+    }
+
+    /// Ajout d'un élément, en écrasant le plus ancien si le buffer est plein
+    pub fn push(&mut self, item: T) {
+        let mut shared = lock(&self.shared);
+        shared.buffer.push(item);
+        shared.written += 1;
+    }
+
+    /// Crée un lecteur qui verra les éléments ajoutés à partir de maintenant
+    pub fn subscribe(&self) -> Reader<T> {
+        Reader {
+            shared: Arc::clone(&self.shared),
+            cursor: lock(&self.shared).written,
+        }
+    }
+
+    /// Retourne la taille actuelle du buffer
+    pub fn len(&self) -> usize {
+        lock(&self.shared).buffer.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        lock(&self.shared).buffer.capacity()
+    }
+}
+
+/// Lecteur d'un [`BroadcastBuffer`], créé par
+/// [`subscribe`](BroadcastBuffer::subscribe).
+///
+/// Cloner un lecteur crée un nouveau lecteur placé au même curseur.
+pub struct Reader<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+    cursor: u64,
+}
+
+impl<T: Clone> Reader<T> {
+    /// Lit l'élément suivant pour ce lecteur
+    ///
+    /// Renvoie [`CircularBufferError::Empty`] si le lecteur est à jour, ou
+    /// [`CircularBufferError::Lagged`] s'il a été dépassé : son curseur est
+    /// alors replacé sur le plus ancien élément encore présent.
+    pub fn try_recv(&mut self) -> Result<T, CircularBufferError> {
+        let shared = lock(&self.shared);
+        let oldest = shared.oldest();
+        if self.cursor < oldest {
+            let missed = oldest - self.cursor;
+            self.cursor = oldest;
+            return Err(CircularBufferError::Lagged(missed));
+        }
+        if self.cursor == shared.written {
+            return Err(CircularBufferError::Empty);
+        }
+        let item = shared.buffer[(self.cursor - oldest) as usize].clone();
+        self.cursor += 1;
+        Ok(item)
+    }
+}
+
+impl<T> Reader<T> {
+    /// Nombre d'éléments restant à lire, y compris ceux déjà écrasés
+    pub fn len(&self) -> usize {
+        (lock(&self.shared).written - self.cursor) as usize
+    }
+
+    /// Vérifie si le lecteur est à jour
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Reader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            cursor: self.cursor,
+        }
+    }
+}
+
+fn lock<T>(shared: &Mutex<Shared<T>>) -> MutexGuard<'_, Shared<T>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chaque_lecteur_voit_tout() {
+        let mut diffusion = BroadcastBuffer::new(4);
+        diffusion.push(0);
+        let mut a = diffusion.subscribe();
+        let mut b = diffusion.subscribe();
+        diffusion.push(1);
+        diffusion.push(2);
+        assert_eq!(a.try_recv(), Ok(1));
+        assert_eq!(a.try_recv(), Ok(2));
+        assert_eq!(a.try_recv(), Err(CircularBufferError::Empty));
+        assert_eq!(b.len(), 2);
+        assert_eq!(b.try_recv(), Ok(1));
+        let mut c = b.clone();
+        assert_eq!(b.try_recv(), Ok(2));
+        assert_eq!(c.try_recv(), Ok(2));
+    }
+
+    #[test]
+    fn test_lecteur_en_retard() {
+        let mut diffusion = BroadcastBuffer::new(2);
+        let mut lent = diffusion.subscribe();
+        for i in 1..=5 {
+            diffusion.push(i);
+        }
+        assert_eq!(lent.try_recv(), Err(CircularBufferError::Lagged(3)));
+        assert_eq!(lent.try_recv(), Ok(4));
+        assert_eq!(lent.try_recv(), Ok(5));
+        assert!(lent.is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+//! Construction d'un [`CircularBuffer`] à partir d'options.
+
+use alloc::boxed::Box;
+
+use crate::{
+    CircularBuffer, CircularBufferError, EvictHook, OverflowPolicy, Watermark, Watermarks,
+};
+
+/// Construit un [`CircularBuffer`] étape par étape, puis vérifie la
+/// configuration avec [`build`](Self::build).
+pub struct CircularBufferBuilder<T> {
+    capacity: Option<usize>,
+    policy: OverflowPolicy,
+    growth_factor: usize,
+    on_evict: Option<EvictHook<T>>,
+    watermarks: Option<Watermarks>,
+}
+
+impl<T> CircularBufferBuilder<T> {
+    /// Création d'un constructeur avec les options par défaut
+    pub fn new() -> Self {
+        Self {
+            capacity: None,
+            policy: OverflowPolicy::default(),
+            growth_factor: 2,
+            on_evict: None,
+            watermarks: None,
+        }
+    }
+
+    /// Capacité initiale du buffer (obligatoire)
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Politique appliquée lorsque le buffer est plein
+    pub fn policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Facteur multipliant la capacité avec [`OverflowPolicy::Grow`] (2 par
+    /// défaut)
+    pub fn growth_factor(mut self, factor: usize) -> Self {
+        self.growth_factor = factor;
+        self
+    }
+
+    /// Fermeture appelée sur chaque élément évincé, voir
+    /// [`CircularBuffer::on_evict`]
+    pub fn on_evict(mut self, f: impl FnMut(T) + Send + 'static) -> Self {
+        self.on_evict = Some(EvictHook(Box::new(f)));
+        self
+    }
+
+    /// Seuils de remplissage et fermeture qui les signale, voir
+    /// [`CircularBuffer::on_watermark`]
+    ///
+    /// # Panics
+    ///
+    /// Panique si `low` n'est pas inférieur à `high`.
+    pub fn on_watermark(
+        mut self,
+        high: usize,
+        low: usize,
+        f: impl FnMut(Watermark) + Send + 'static,
+    ) -> Self {
+        self.watermarks = Some(Watermarks::new(high, low, Box::new(f)));
+        self
+    }
+
+    /// Vérifie la configuration et construit le buffer
+    pub fn build(self) -> Result<CircularBuffer<T>, CircularBufferError> {
+        let capacity = self.capacity.ok_or(CircularBufferError::MissingCapacity)?;
+        if capacity == 0 {
+            return Err(CircularBufferError::ZeroCapacity);
+        }
+        if self.growth_factor < 2 {
+            return Err(CircularBufferError::InvalidGrowthFactor(self.growth_factor));
+        }
+        let mut buffer = CircularBuffer::with_policy(capacity, self.policy);
+        buffer.growth_factor = self.growth_factor;
+        buffer.on_evict = self.on_evict;
+        buffer.watermarks = self.watermarks;
+        Ok(buffer)
+    }
+}
+
+impl<T> Default for CircularBufferBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_complet() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let evictions = Arc::new(AtomicUsize::new(0));
+        let compteur = Arc::clone(&evictions);
+        let mut buffer = CircularBuffer::builder()
+            .capacity(2)
+            .on_evict(move |_: i32| {
+                compteur.fetch_add(1, Ordering::Relaxed);
+            })
+            .build()
+            .unwrap();
+        buffer.extend([1, 2, 3]);
+        assert_eq!(evictions.load(Ordering::Relaxed), 1);
+        assert_eq!(buffer.policy(), OverflowPolicy::Overwrite);
+    }
+
+    #[test]
+    fn test_facteur_de_croissance() {
+        let mut buffer = CircularBuffer::builder()
+            .capacity(2)
+            .policy(OverflowPolicy::Grow)
+            .growth_factor(3)
+            .build()
+            .unwrap();
+        buffer.extend([1, 2, 3]);
+        assert_eq!(buffer.capacity(), 6);
+        buffer.extend_from_slice(&[4, 5, 6, 7]);
+        assert_eq!(buffer.capacity(), 18);
+        assert_eq!(buffer.clone().capacity(), 18);
+    }
+
+    #[test]
+    fn test_configuration_invalide() {
+        let erreur = |b: CircularBufferBuilder<i32>| b.build().err();
+        assert_eq!(
+            erreur(CircularBufferBuilder::new()),
+            Some(CircularBufferError::MissingCapacity)
+        );
+        assert_eq!(
+            erreur(CircularBuffer::builder().capacity(0)),
+            Some(CircularBufferError::ZeroCapacity)
+        );
+        assert_eq!(
+            erreur(CircularBuffer::builder().capacity(4).growth_factor(1)),
+            Some(CircularBufferError::InvalidGrowthFactor(1))
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Buffer circulaire d'octets utilisable comme `io::Read` / `io::Write`
+//! (fonctionnalité `std`).
+//!
+//! Avec la fonctionnalité `tokio`, [`AsyncByteRing`] partage un tel buffer
+//! entre tâches et implémente `AsyncRead` / `AsyncWrite`.
+
+use std::io::{self, BufRead, Read, Write};
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(feature = "tokio")]
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll, Waker};
+
+use crate::{assume_init_slice_mut, CircularBuffer, OverflowPolicy};
+
+/// Buffer d'octets borné, à placer par exemple entre la lecture d'une socket
+/// et un analyseur.
+///
+/// Une écriture ne recopie que ce qui tient dans la place libre et n'écrase
+/// jamais : si le buffer est plein, elle échoue avec
+/// `io::ErrorKind::WouldBlock`. Une lecture sur un buffer vide renvoie `0`.
+///
+/// Pour éviter la copie intermédiaire, [`grant`](Self::grant) prête
+/// directement la place libre (à remplir par un DMA ou `Read::read`, puis à
+/// valider avec [`commit`](Self::commit)), et
+/// [`read_slice`](Self::read_slice) prête les octets en attente, à libérer
+/// avec [`release`](Self::release).
+pub struct ByteRingBuffer {
+    buffer: CircularBuffer<u8>,
+    // Taille de la dernière zone prêtée par `grant`
+    granted: usize,
+}
+
+impl ByteRingBuffer {
+    /// Création d'un buffer vide de `capacity` octets
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        let mut buffer = CircularBuffer::with_policy(capacity, OverflowPolicy::Reject);
+        // Toutes les cases sont initialisées une fois pour toutes, ce qui
+        // permet à `grant` de prêter la place libre comme `&mut [u8]`
+        for slot in buffer.buffer.iter_mut() {
+            slot.write(0);
+        }
+        Self { buffer, granted: 0 }
+    }
+
+    /// Nombre d'octets en attente de lecture
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Nombre d'octets pouvant encore être écrits
+    pub fn free(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Octets en attente, en deux morceaux contigus, voir
+    /// [`CircularBuffer::as_slices`]
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_slices()
+    }
+
+    /// Vide complètement le buffer
+    pub fn clear(&mut self) {
+        self.granted = 0;
+        self.buffer.clear();
+    }
+
+    /// Prête jusqu'à `n` octets libres et contigus à partir de la position
+    /// d'écriture ; la tranche peut être plus courte que `n`, voire vide
+    ///
+    /// Les octets écrits ne font partie du contenu qu'après
+    /// [`commit`](Self::commit). Toute autre écriture, lecture ou libération
+    /// annule la zone prêtée.
+    pub fn grant(&mut self, n: usize) -> &mut [u8] {
+        let b = &mut self.buffer;
+        if b.is_empty() {
+            // Repartir du début du stockage offre la plus grande zone contiguë
+            b.start = 0;
+        }
+        let head = b.head_index();
+        let end = if b.is_full() {
+            head
+        } else if head < b.start {
+            b.start
+        } else {
+            b.capacity()
+        };
+        let len = n.min(end - head);
+        self.granted = len;
+        // SAFETY: toutes les cases sont initialisées depuis `new`, et la zone
+        // prêtée est libre.
+        unsafe { assume_init_slice_mut(&mut b.buffer[head..head + len]) }
+    }
+
+    /// Ajoute au contenu les `n` premiers octets de la dernière zone prêtée
+    /// par [`grant`](Self::grant)
+    ///
+    /// # Panics
+    ///
+    /// Panique si `n` dépasse la taille de la zone prêtée, ou si la zone a
+    /// été annulée depuis.
+    pub fn commit(&mut self, n: usize) {
+        assert!(
+            n <= self.granted,
+            "Validation de {} octets pour une zone de {} octets.",
+            n,
+            self.granted
+        );
+        assert!(
+            n <= self.free(),
+            "Validation de {} octets pour {} octets libres.",
+            n,
+            self.free()
+        );
+        self.buffer.head = self.buffer.head.wrapping_add(n as u64);
+        self.buffer.record_pushes(n);
+        self.granted = 0;
+    }
+
+    /// Prête les plus anciens octets en attente, contigus dans le stockage ;
+    /// la tranche est vide si le buffer est vide
+    pub fn read_slice(&self) -> &[u8] {
+        self.buffer.as_slices().0
+    }
+
+    /// Retire les `n` plus anciens octets, typiquement après
+    /// [`read_slice`](Self::read_slice)
+    pub fn release(&mut self, n: usize) {
+        self.granted = 0;
+        self.buffer.release_front(n);
+    }
+}
+
+/// Les octets sont copiés vers `buf` ; voir [`ByteRingBuffer::read_slice`] pour un
+/// accès sans copie.
+impl Read for ByteRingBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.granted = 0;
+        Ok(self.buffer.pop_into(buf))
+    }
+}
+
+impl BufRead for ByteRingBuffer {
+    /// Renvoie le premier morceau contigu des octets en attente
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.buffer.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.granted = 0;
+        self.buffer.release_front(amt);
+    }
+}
+
+impl Write for ByteRingBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !buf.is_empty() && self.is_full() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.granted = 0;
+        Ok(self.buffer.push_slice(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+struct Shared {
+    ring: ByteRingBuffer,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+    // L'écriture a été fermée par `poll_shutdown`
+    shut_down: bool,
+    // L'extrémité de `Duplex` qui lisait ce buffer a été abandonnée
+    read_closed: bool,
+}
+
+/// [`ByteRingBuffer`] partagé entre tâches, utilisable comme tuyau en mémoire
+/// (fonctionnalité `tokio`).
+///
+/// Les clones désignent le même buffer : l'un écrit avec `AsyncWrite`, un
+/// autre lit avec `AsyncRead`. Une lecture sur un buffer vide attend qu'une
+/// écriture la réveille ; après `shutdown`, elle renvoie `0` une fois le
+/// buffer vidé. Une écriture sur un buffer plein attend une lecture.
+#[cfg(feature = "tokio")]
+#[derive(Clone)]
+pub struct AsyncByteRing {
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncByteRing {
+    /// Création d'un buffer partagé vide de `capacity` octets
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(Mutex::new(Shared {
+                ring: ByteRingBuffer::new(capacity),
+                read_waker: None,
+                write_waker: None,
+                shut_down: false,
+                read_closed: false,
+            })),
+        }
+    }
+
+    /// Crée deux extrémités reliées par deux buffers de `capacity` octets :
+    /// ce qui est écrit sur l'une est lu sur l'autre
+    pub fn duplex(capacity: usize) -> (Duplex, Duplex) {
+        let (a, b) = (Self::new(capacity), Self::new(capacity));
+        (
+            Duplex {
+                read: a.clone(),
+                write: b.clone(),
+            },
+            Duplex { read: b, write: a },
+        )
+    }
+
+    /// Nombre d'octets en attente de lecture
+    pub fn len(&self) -> usize {
+        self.lock().ring.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.lock().ring.is_empty()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.lock().ring.capacity()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Shared> {
+        self.shared.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Ferme l'écriture : le lecteur recevra la fin du flux
+    fn shut_down(&self) {
+        let mut shared = self.lock();
+        shared.shut_down = true;
+        if let Some(waker) = shared.read_waker.take() {
+            waker.wake();
+        }
+    }
+
+    // Plus personne ne lit : les écritures échoueront
+    fn close_read(&self) {
+        let mut shared = self.lock();
+        shared.read_closed = true;
+        if let Some(waker) = shared.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for AsyncByteRing {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let mut shared = self.lock();
+        if shared.ring.is_empty() && buf.remaining() > 0 {
+            if shared.shut_down {
+                return Poll::Ready(Ok(()));
+            }
+            shared.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let n = Read::read(&mut shared.ring, buf.initialize_unfilled())?;
+        buf.advance(n);
+        if let Some(waker) = shared.write_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncWrite for AsyncByteRing {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut shared = self.lock();
+        if shared.shut_down || shared.read_closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        match shared.ring.write(buf) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                shared.write_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            result => {
+                if let Some(waker) = shared.read_waker.take() {
+                    waker.wake();
+                }
+                Poll::Ready(result)
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.shut_down();
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Extrémité d'un tuyau bidirectionnel créé par [`AsyncByteRing::duplex`].
+///
+/// Abandonner une extrémité ferme le tuyau : l'autre lit la fin du flux une
+/// fois les octets restants reçus, et ses écritures échouent avec
+/// `io::ErrorKind::BrokenPipe`.
+#[cfg(feature = "tokio")]
+pub struct Duplex {
+    read: AsyncByteRing,
+    write: AsyncByteRing,
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for Duplex {
+    fn drop(&mut self) {
+        self.write.shut_down();
+        self.read.close_read();
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for Duplex {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.read).poll_read(cx, buf)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncWrite for Duplex {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.write).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.write).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.write).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecriture_partielle() {
+        let mut tampon = ByteRingBuffer::new(4);
+        assert_eq!(tampon.write(b"abcdef").unwrap(), 4);
+        let erreur = tampon.write(b"ef").unwrap_err();
+        assert_eq!(erreur.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(tampon.write(b"").unwrap(), 0);
+
+        let mut lu = [0; 3];
+        assert_eq!(tampon.read(&mut lu).unwrap(), 3);
+        assert_eq!(&lu, b"abc");
+        tampon.write_all(b"ef").unwrap();
+        let mut reste = Vec::new();
+        tampon.read_to_end(&mut reste).unwrap();
+        assert_eq!(reste, b"def");
+        assert!(tampon.is_empty());
+    }
+
+    #[test]
+    fn test_buf_read_lignes() {
+        let mut tampon = ByteRingBuffer::new(6);
+        tampon.write_all(b"un\ndeu").unwrap();
+        let mut ligne = String::new();
+        tampon.read_line(&mut ligne).unwrap();
+        assert_eq!(ligne, "un\n");
+
+        // La suite de la ligne s'enroule en début de stockage
+        tampon.write_all(b"x\n").unwrap();
+        assert_eq!(tampon.fill_buf().unwrap(), b"deu");
+        ligne.clear();
+        tampon.read_line(&mut ligne).unwrap();
+        assert_eq!(ligne, "deux\n");
+        assert_eq!(tampon.free(), 6);
+    }
+
+    #[test]
+    fn test_grant_commit_sans_copie() {
+        let mut tampon = ByteRingBuffer::new(8);
+        let mut source: &[u8] = b"entete:corps";
+        let n = source.read(tampon.grant(8)).unwrap();
+        tampon.commit(n);
+        assert_eq!(tampon.read_slice(), b"entete:c");
+        assert!(tampon.grant(4).is_empty());
+
+        tampon.release(7);
+        // La zone libre reprend au début du stockage, avant l'octet restant
+        let zone = tampon.grant(8);
+        assert_eq!(zone.len(), 7);
+        zone[..4].copy_from_slice(b"orps");
+        tampon.commit(4);
+        assert_eq!(tampon.read_slice(), b"c");
+        tampon.release(1);
+        assert_eq!(tampon.read_slice(), b"orps");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_trop_grand() {
+        let mut tampon = ByteRingBuffer::new(4);
+        tampon.grant(2);
+        tampon.commit(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_apres_ecriture() {
+        let mut tampon = ByteRingBuffer::new(4);
+        tampon.grant(4);
+        tampon.write_all(b"abcd").unwrap();
+        tampon.commit(4);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_tuyau_asynchrone() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let tuyau = AsyncByteRing::new(4);
+        let mut ecriture = tuyau.clone();
+        let envoi = tokio::spawn(async move {
+            ecriture.write_all(b"bonjour le monde").await.unwrap();
+            ecriture.shutdown().await.unwrap();
+        });
+        let mut lecture = tuyau;
+        let mut recu = Vec::new();
+        lecture.read_to_end(&mut recu).await.unwrap();
+        envoi.await.unwrap();
+        assert_eq!(recu, b"bonjour le monde");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_duplex() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (mut client, mut serveur) = AsyncByteRing::duplex(8);
+        client.write_all(b"ping").await.unwrap();
+        let mut requete = [0; 4];
+        serveur.read_exact(&mut requete).await.unwrap();
+        assert_eq!(&requete, b"ping");
+        serveur.write_all(b"pong").await.unwrap();
+        serveur.shutdown().await.unwrap();
+        let mut reponse = String::new();
+        client.read_to_string(&mut reponse).await.unwrap();
+        assert_eq!(reponse, "pong");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_duplex_abandonne() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let (mut client, mut serveur) = AsyncByteRing::duplex(8);
+        // Écriture bloquée sur un buffer plein pendant l'abandon du serveur
+        let envoi = tokio::spawn(async move {
+            let resultat = client.write_all(&[1; 16]).await;
+            (client, resultat)
+        });
+        let mut debut = [0; 4];
+        serveur.read_exact(&mut debut).await.unwrap();
+        serveur.write_all(b"fin").await.unwrap();
+        drop(serveur);
+        let (mut client, resultat) = envoi.await.unwrap();
+        assert_eq!(resultat.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        let mut reponse = Vec::new();
+        client.read_to_end(&mut reponse).await.unwrap();
+        assert_eq!(reponse, b"fin");
+    }
+}
@@ -0,0 +1,164 @@
+//! Conversions entre [`CircularBuffer`] et les collections de `alloc`, et
+//! collecte des derniers éléments d'un itérateur.
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::{CircularBuffer, CircularBufferError};
+
+impl<T: Clone> CircularBuffer<T> {
+    /// Copie les éléments dans un `Vec`, du plus ancien au plus récent
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+
+    /// Création d'un buffer de capacité `capacity` contenant une copie de
+    /// `items`, sans paniquer
+    ///
+    /// Renvoie [`CircularBufferError::ZeroCapacity`] si `capacity` vaut 0 et
+    /// [`CircularBufferError::NotEnoughSpace`] si `items` ne tient pas.
+    pub fn try_from_slice(items: &[T], capacity: usize) -> Result<Self, CircularBufferError> {
+        if items.len() > capacity {
+            return Err(CircularBufferError::NotEnoughSpace {
+                needed: items.len(),
+                available: capacity,
+            });
+        }
+        let mut buffer = Self::try_new(capacity)?;
+        buffer.extend(items.iter().cloned());
+        Ok(buffer)
+    }
+}
+
+/// Le buffer obtenu est plein : sa capacité est la longueur du `Vec` (au
+/// moins 1), comme pour [`FromIterator`].
+impl<T> From<Vec<T>> for CircularBuffer<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::from_boxed_slice(items.into_boxed_slice())
+    }
+}
+
+/// Le buffer obtenu est plein : sa capacité est la longueur du tableau (au
+/// moins 1).
+impl<T, const N: usize> From<[T; N]> for CircularBuffer<T> {
+    fn from(items: [T; N]) -> Self {
+        Self::from_boxed_slice(Box::new(items))
+    }
+}
+
+/// Le buffer obtenu est plein : sa capacité est la longueur de la tranche.
+/// Une tranche vide renvoie [`CircularBufferError::ZeroCapacity`].
+impl<T: Clone> TryFrom<&[T]> for CircularBuffer<T> {
+    type Error = CircularBufferError;
+
+    fn try_from(items: &[T]) -> Result<Self, CircularBufferError> {
+        Self::try_from_slice(items, items.len())
+    }
+}
+
+/// Le buffer obtenu est plein, dans l'ordre de la file.
+impl<T> From<VecDeque<T>> for CircularBuffer<T> {
+    fn from(items: VecDeque<T>) -> Self {
+        Vec::from(items).into()
+    }
+}
+
+/// Les éléments sont rangés du plus ancien au plus récent ; un buffer plein
+/// cède son stockage sans copie.
+impl<T> From<CircularBuffer<T>> for Vec<T> {
+    fn from(mut buffer: CircularBuffer<T>) -> Self {
+        if !buffer.is_full() {
+            return buffer.into_iter().collect();
+        }
+        buffer.make_contiguous();
+        // Le buffer vidé n'a plus rien à libérer à son abandon
+        let storage = mem::take(&mut buffer.buffer);
+        buffer.tail = buffer.head;
+        buffer.start = 0;
+        // SAFETY: le buffer était plein, donc toutes les cases sont
+        // initialisées, et `MaybeUninit<T>` a la même disposition que `T`.
+        let items = unsafe { Box::from_raw(Box::into_raw(storage) as *mut [T]) };
+        items.into_vec()
+    }
+}
+
+impl<T> From<CircularBuffer<T>> for VecDeque<T> {
+    fn from(buffer: CircularBuffer<T>) -> Self {
+        Vec::from(buffer).into()
+    }
+}
+
+/// Collecte des derniers éléments d'un itérateur dans un [`CircularBuffer`].
+pub trait RingCollect: Iterator + Sized {
+    /// Parcourt tout l'itérateur et ne garde que ses `n` derniers éléments,
+    /// sans jamais en retenir plus de `n`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `n` vaut 0.
+    fn collect_last(self, n: usize) -> CircularBuffer<Self::Item> {
+        CircularBuffer::from_iter_with_capacity(n, self)
+    }
+}
+
+impl<I: Iterator> RingCollect for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_conversions() {
+        let mut buffer = CircularBuffer::from(vec![1, 2, 3]);
+        assert_eq!(buffer.capacity(), 3);
+        buffer.push(4);
+        assert_eq!(buffer.to_vec(), [2, 3, 4]);
+        assert_eq!(Vec::from(buffer), [2, 3, 4]);
+
+        let mut partiel = CircularBuffer::new(4);
+        partiel.extend([5, 6]);
+        let file = VecDeque::from(partiel);
+        assert_eq!(file, [5, 6]);
+        let retour = CircularBuffer::from(file);
+        assert_eq!(retour, [5, 6][..]);
+        assert!(retour.is_full());
+    }
+
+    #[test]
+    fn test_depuis_tableau_et_tranche() {
+        let buffer = CircularBuffer::from([1, 2, 3]);
+        assert_eq!(buffer.capacity(), 3);
+        assert_eq!(buffer, [1, 2, 3]);
+
+        let tranche: &[i32] = &[4, 5];
+        let buffer = CircularBuffer::try_from(tranche).unwrap();
+        assert!(buffer.is_full());
+        assert_eq!(buffer, [4, 5]);
+        let vide: &[i32] = &[];
+        assert_eq!(
+            CircularBuffer::try_from(vide).err(),
+            Some(CircularBufferError::ZeroCapacity)
+        );
+
+        let buffer = CircularBuffer::try_from_slice(tranche, 4).unwrap();
+        assert_eq!((buffer.len(), buffer.capacity()), (2, 4));
+        assert_eq!(
+            CircularBuffer::try_from_slice(tranche, 1).err(),
+            Some(CircularBufferError::NotEnoughSpace {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_collect_last() {
+        let lignes = "un\ndeux\ntrois\nquatre".lines();
+        assert_eq!(lignes.collect_last(2), ["trois", "quatre"]);
+        let courts = (0..2).collect_last(5);
+        assert_eq!((courts.len(), courts.capacity()), (2, 5));
+    }
+}
@@ -0,0 +1,115 @@
+//! Buffer partagé copié seulement à la première modification.
+
+use alloc::sync::Arc;
+use core::ops::Deref;
+
+use crate::CircularBuffer;
+
+/// Poignée sur un [`CircularBuffer`] dont le clonage partage le stockage.
+///
+/// Cloner la poignée ne copie rien ; la première modification à travers une
+/// poignée partagée copie le buffer, comme [`Clone`] pour un
+/// [`CircularBuffer`], et les autres poignées gardent l'ancien contenu. La
+/// lecture passe par `Deref`.
+pub struct CowCircularBuffer<T> {
+    buffer: Arc<CircularBuffer<T>>,
+}
+
+impl<T: Clone> CowCircularBuffer<T> {
+    /// Création d'une poignée sur un buffer vide de `size` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `size` vaut 0.
+    pub fn new(size: usize) -> Self {
+        CircularBuffer::new(size).into()
+    }
+
+    /// Retourne le buffer modifiable, après l'avoir copié s'il est partagé
+    pub fn make_mut(&mut self) -> &mut CircularBuffer<T> {
+        Arc::make_mut(&mut self.buffer)
+    }
+
+    /// Ajout d'un élément, comme [`CircularBuffer::push`]
+    pub fn push(&mut self, item: T) -> Option<T> {
+        self.make_mut().push(item)
+    }
+
+    /// Retrait de l'élément le plus ancien, comme [`CircularBuffer::pop`]
+    pub fn pop(&mut self) -> Option<T> {
+        self.make_mut().pop()
+    }
+
+    /// Vide le buffer ; une poignée partagée repart d'un buffer neuf au lieu
+    /// de copier un contenu aussitôt supprimé
+    pub fn clear(&mut self) {
+        match Arc::get_mut(&mut self.buffer) {
+            Some(buffer) => buffer.clear(),
+            None => {
+                let mut buffer = CircularBuffer::with_policy(self.capacity(), self.policy());
+                buffer.growth_factor = self.buffer.growth_factor;
+                self.buffer = Arc::new(buffer);
+            }
+        }
+    }
+
+    /// Vérifie si le stockage est partagé avec une autre poignée
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.buffer) > 1
+    }
+
+    /// Retourne le buffer, copié s'il est encore partagé
+    pub fn into_inner(self) -> CircularBuffer<T> {
+        Arc::unwrap_or_clone(self.buffer)
+    }
+}
+
+impl<T> Clone for CowCircularBuffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: Arc::clone(&self.buffer),
+        }
+    }
+}
+
+impl<T> Deref for CowCircularBuffer<T> {
+    type Target = CircularBuffer<T>;
+
+    fn deref(&self) -> &CircularBuffer<T> {
+        &self.buffer
+    }
+}
+
+impl<T> From<CircularBuffer<T>> for CowCircularBuffer<T> {
+    fn from(buffer: CircularBuffer<T>) -> Self {
+        Self {
+            buffer: Arc::new(buffer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_copie_a_la_premiere_modification() {
+        let mut original = CowCircularBuffer::new(3);
+        original.push(1);
+        original.push(2);
+        let mut copie = original.clone();
+        assert!(original.is_shared());
+        assert_eq!(copie.iter().collect::<Vec<_>>(), [&1, &2]);
+
+        copie.push(3);
+        assert!(!original.is_shared());
+        assert_eq!(original.len(), 2);
+        assert_eq!(copie.len(), 3);
+
+        let mut autre = copie.clone();
+        autre.clear();
+        assert!(autre.is_empty());
+        assert_eq!(copie.into_inner().iter().collect::<Vec<_>>(), [&1, &2, &3]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Curseurs de lecture indépendants sur un [`CircularBuffer`].
+
+use crate::{CircularBuffer, CircularBufferError, Storage};
+
+/// Position de lecture propre à un lecteur, obtenue par
+/// [`CircularBuffer::cursor`].
+///
+/// Le curseur ne retire rien : plusieurs curseurs et un consommateur qui
+/// appelle `pop` peuvent parcourir le même contenu. Il ne garde aucun emprunt
+/// sur le buffer, qui reste modifiable entre deux lectures ; si les éléments
+/// de sa position ont été écrasés ou retirés entre-temps, la lecture suivante
+/// le signale par [`CircularBufferError::Lagged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    position: u64,
+}
+
+impl Cursor {
+    pub(crate) fn new(position: u64) -> Self {
+        Self { position }
+    }
+
+    /// Retourne la position absolue du prochain élément lu
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Lit l'élément suivant sans le retirer, ou `Ok(None)` si le curseur a
+    /// rattrapé le plus récent
+    ///
+    /// Si des éléments ont disparu avant d'être lus, renvoie `Lagged` avec
+    /// leur nombre et replace le curseur sur le plus ancien élément restant.
+    pub fn next<'a, T, S: Storage<T>>(
+        &mut self,
+        buffer: &'a CircularBuffer<T, S>,
+    ) -> Result<Option<&'a T>, CircularBufferError> {
+        let missed = buffer.tail.wrapping_sub(self.position);
+        if (missed as i64) > 0 {
+            self.position = buffer.tail;
+            return Err(CircularBufferError::Lagged(missed));
+        }
+        let index = self.position.wrapping_sub(buffer.tail) as usize;
+        if index >= buffer.len() {
+            // Rester sur "head" même si des éléments ont été retirés à l'arrière
+            self.position = buffer.head;
+            return Ok(None);
+        }
+        self.position = self.position.wrapping_add(1);
+        Ok(buffer.get(index))
+    }
+
+    /// Nombre d'éléments restant à lire, ou `None` si le curseur a été dépassé
+    pub fn remaining<T, S: Storage<T>>(&self, buffer: &CircularBuffer<T, S>) -> Option<usize> {
+        let index = self.position.wrapping_sub(buffer.tail);
+        if (index as i64) < 0 {
+            return None;
+        }
+        Some(buffer.len().saturating_sub(index as usize))
+    }
+
+    /// Replace le curseur sur le plus ancien élément du buffer
+    pub fn rewind<T, S: Storage<T>>(&mut self, buffer: &CircularBuffer<T, S>) {
+        self.position = buffer.tail;
+    }
+
+    /// Place le curseur après le plus récent élément : seuls les ajouts
+    /// suivants seront lus, à la manière de `tail -f`
+    pub fn seek_to_end<T, S: Storage<T>>(&mut self, buffer: &CircularBuffer<T, S>) {
+        self.position = buffer.head;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lecture_sans_retrait() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend([1, 2, 3]);
+        let mut a = buffer.cursor();
+        let mut b = buffer.cursor();
+        assert_eq!(a.next(&buffer), Ok(Some(&1)));
+        assert_eq!(a.next(&buffer), Ok(Some(&2)));
+        assert_eq!(b.next(&buffer), Ok(Some(&1)));
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(a.remaining(&buffer), Some(1));
+        assert_eq!(a.next(&buffer), Ok(Some(&3)));
+        assert_eq!(a.next(&buffer), Ok(None));
+        buffer.push(4);
+        assert_eq!(a.next(&buffer), Ok(Some(&4)));
+
+        let mut fin = buffer.cursor();
+        fin.seek_to_end(&buffer);
+        assert_eq!(fin.next(&buffer), Ok(None));
+        buffer.push(5);
+        assert_eq!(fin.next(&buffer), Ok(Some(&5)));
+    }
+
+    #[test]
+    fn test_curseur_depasse() {
+        let mut buffer = CircularBuffer::new(2);
+        buffer.extend([1, 2]);
+        let mut curseur = buffer.cursor();
+        buffer.extend([3, 4, 5]);
+        assert_eq!(curseur.remaining(&buffer), None);
+        assert_eq!(curseur.next(&buffer), Err(CircularBufferError::Lagged(3)));
+        assert_eq!(curseur.next(&buffer), Ok(Some(&4)));
+        assert_eq!(curseur.position(), 4);
+    }
+}
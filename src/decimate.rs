@@ -0,0 +1,139 @@
+//! Buffer qui divise sa résolution par deux au lieu d'écraser ses éléments.
+
+use crate::{CircularBuffer, Iter, OverflowPolicy};
+
+/// Buffer de capacité fixe qui couvre toujours tout le flux reçu.
+///
+/// Au lieu d'écraser le plus ancien, un buffer plein ne garde qu'un élément
+/// sur deux et n'accepte plus ensuite qu'un élément reçu sur deux : après `k`
+/// réductions, il conserve un élément sur `2^k` (le pas, voir
+/// [`stride`](Self::stride)), régulièrement espacés depuis le premier.
+pub struct DecimatingBuffer<T> {
+    items: CircularBuffer<T>,
+    // Nombre d'éléments reçus, gardés ou non
+    received: u64,
+    stride: u64,
+}
+
+impl<T> DecimatingBuffer<T> {
+    /// Création d'un buffer vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` est inférieure à 2.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity >= 2,
+            "La taille du buffer doit être au moins 2 pour pouvoir être réduite."
+        );
+        Self {
+            items: CircularBuffer::with_policy(capacity, OverflowPolicy::Reject),
+            received: 0,
+            stride: 1,
+        }
+    }
+
+    /// Ajout d'un élément ; un élément qui ne tombe pas sur le pas courant
+    /// n'est pas gardé et il est rendu
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let index = self.received;
+        self.received += 1;
+        if !index.is_multiple_of(self.stride) {
+            return Some(item);
+        }
+        if self.items.is_full() {
+            self.decimate();
+            if !index.is_multiple_of(self.stride) {
+                return Some(item);
+            }
+        }
+        self.items.push(item)
+    }
+
+    /// Retourne le pas entre deux éléments gardés, en nombre d'éléments reçus
+    pub fn stride(&self) -> u64 {
+        self.stride
+    }
+
+    /// Retourne le nombre d'éléments reçus, gardés ou non
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Parcourt les éléments gardés, du plus ancien au plus récent
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Parcourt les éléments gardés avec leur rang dans le flux reçu
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (u64, &T)> {
+        let stride = self.stride;
+        self.items
+            .iter()
+            .enumerate()
+            .map(move |(i, item)| (i as u64 * stride, item))
+    }
+
+    /// Retourne le nombre d'éléments gardés
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Vide le buffer et revient à la pleine résolution
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.received = 0;
+        self.stride = 1;
+    }
+
+    // Ne garde que les éléments de rang pair et double le pas
+    fn decimate(&mut self) {
+        let mut rank = 0;
+        self.items.retain(|_| {
+            rank += 1;
+            rank % 2 == 1
+        });
+        self.stride *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_reductions_successives() {
+        let mut capture = DecimatingBuffer::new(4);
+        for i in 0..4 {
+            assert_eq!(capture.push(i), None);
+        }
+        assert_eq!(capture.push(4), None);
+        assert_eq!(capture.stride(), 2);
+        assert_eq!(capture.iter().copied().collect::<Vec<_>>(), [0, 2, 4]);
+        assert_eq!(capture.push(5), Some(5));
+        for i in 6..16 {
+            capture.push(i);
+        }
+        assert_eq!(capture.stride(), 4);
+        assert_eq!(capture.iter().copied().collect::<Vec<_>>(), [0, 4, 8, 12]);
+        assert_eq!(
+            capture
+                .iter_indexed()
+                .map(|(rang, _)| rang)
+                .collect::<Vec<_>>(),
+            [0, 4, 8, 12]
+        );
+        assert_eq!(capture.received(), 16);
+    }
+}
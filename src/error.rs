@@ -0,0 +1,73 @@
+//! Erreurs renvoyées par les opérations faillibles du buffer.
+
+use core::fmt;
+
+/// Erreur d'une opération sur un buffer circulaire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CircularBufferError {
+    /// Une capacité nulle a été demandée.
+    ZeroCapacity,
+    /// Aucune capacité n'a été fournie au constructeur.
+    MissingCapacity,
+    /// Le facteur de croissance est inférieur à 2.
+    InvalidGrowthFactor(usize),
+    /// Le buffer est plein.
+    Full,
+    /// Le buffer est vide.
+    Empty,
+    /// L'indice logique dépasse le nombre d'éléments.
+    IndexOutOfBounds {
+        /// Indice demandé.
+        index: usize,
+        /// Nombre d'éléments du buffer.
+        len: usize,
+    },
+    /// Un lot d'éléments ne tient pas entièrement dans le buffer.
+    NotEnoughSpace {
+        /// Nombre de cases demandées.
+        needed: usize,
+        /// Nombre de cases disponibles.
+        available: usize,
+    },
+    /// Le stockage ne permet pas de changer la capacité.
+    FixedCapacity,
+    /// Le buffer est vide et ne recevra plus d'éléments.
+    Closed,
+    /// Un lecteur a été dépassé par le producteur et a manqué ce nombre
+    /// d'éléments.
+    Lagged(u64),
+}
+
+impl fmt::Display for CircularBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroCapacity => f.write_str("La taille du buffer doit être positive."),
+            Self::MissingCapacity => f.write_str("La capacité du buffer doit être précisée."),
+            Self::InvalidGrowthFactor(factor) => {
+                write!(
+                    f,
+                    "Le facteur de croissance doit être au moins 2 (reçu {}).",
+                    factor
+                )
+            }
+            Self::Full => f.write_str("Le buffer est plein."),
+            Self::Empty => f.write_str("Le buffer est vide."),
+            Self::FixedCapacity => f.write_str("La capacité de ce stockage est fixe."),
+            Self::Closed => f.write_str("Le buffer est fermé."),
+            Self::IndexOutOfBounds { index, len } => {
+                write!(f, "Indice {} hors limites (taille {}).", index, len)
+            }
+            Self::NotEnoughSpace { needed, available } => write!(
+                f,
+                "Place insuffisante : {} cases demandées, {} disponibles.",
+                needed, available
+            ),
+            Self::Lagged(missed) => {
+                write!(f, "Lecteur en retard : {} éléments manqués.", missed)
+            }
+        }
+    }
+}
+
+impl core::error::Error for CircularBufferError {}
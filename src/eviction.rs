@@ -0,0 +1,256 @@
+//! Stratégies d'éviction interchangeables pour un buffer plein.
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+/// Élément écarté par une [`EvictionPolicy`] lorsque le buffer est plein.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Victim {
+    /// L'élément entrant n'est pas ajouté.
+    Incoming,
+    /// L'élément stocké à cet indice logique, 0 étant le plus ancien, est
+    /// retiré ; l'élément entrant est ajouté en queue.
+    Index(usize),
+}
+
+/// Choisit l'élément à écarter quand un [`EvictingBuffer`] plein reçoit un
+/// nouvel élément.
+///
+/// Toute fermeture `FnMut(&CircularBuffer<T>, &T) -> Victim` est une
+/// stratégie.
+pub trait EvictionPolicy<T> {
+    /// Retourne l'élément à écarter entre ceux de `items`, plein, et `incoming`
+    fn victim(&mut self, items: &CircularBuffer<T>, incoming: &T) -> Victim;
+}
+
+impl<T, F: FnMut(&CircularBuffer<T>, &T) -> Victim> EvictionPolicy<T> for F {
+    fn victim(&mut self, items: &CircularBuffer<T>, incoming: &T) -> Victim {
+        self(items, incoming)
+    }
+}
+
+/// Écarte le plus ancien élément : le comportement d'un buffer circulaire.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fifo;
+
+impl<T> EvictionPolicy<T> for Fifo {
+    fn victim(&mut self, _items: &CircularBuffer<T>, _incoming: &T) -> Victim {
+        Victim::Index(0)
+    }
+}
+
+/// Écarte le plus récent élément stocké, pour garder le début du flux en
+/// plus du dernier élément reçu.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lifo;
+
+impl<T> EvictionPolicy<T> for Lifo {
+    fn victim(&mut self, items: &CircularBuffer<T>, _incoming: &T) -> Victim {
+        Victim::Index(items.len() - 1)
+    }
+}
+
+/// Écarte un élément stocké choisi au hasard.
+///
+/// Le générateur pseudo-aléatoire (xorshift) est déterministe pour une
+/// graine donnée ; il ne convient pas à un usage cryptographique.
+#[derive(Debug, Clone)]
+pub struct Random {
+    rng: XorShift,
+}
+
+impl Random {
+    /// Création d'une stratégie dont le tirage dépend de `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: XorShift::new(seed),
+        }
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl<T> EvictionPolicy<T> for Random {
+    fn victim(&mut self, items: &CircularBuffer<T>, _incoming: &T) -> Victim {
+        Victim::Index(self.rng.below(items.len() as u64) as usize)
+    }
+}
+
+/// Écarte l'élément dont la clé est la plus petite, le plus ancien en cas
+/// d'égalité ; un élément entrant de clé strictement plus petite n'est pas
+/// ajouté.
+#[derive(Debug, Clone, Copy)]
+pub struct LowestBy<F>(pub F);
+
+impl<T, K: Ord, F: FnMut(&T) -> K> EvictionPolicy<T> for LowestBy<F> {
+    fn victim(&mut self, items: &CircularBuffer<T>, incoming: &T) -> Victim {
+        let key = &mut self.0;
+        // `min_by` garde le premier minimum, donc le plus ancien
+        let lowest = items
+            .iter()
+            .map(&mut *key)
+            .enumerate()
+            .min_by(|a, b| a.1.cmp(&b.1));
+        match lowest {
+            Some((index, lowest)) if key(incoming) >= lowest => Victim::Index(index),
+            _ => Victim::Incoming,
+        }
+    }
+}
+
+/// Buffer de capacité fixe dont la [`EvictionPolicy`] décide de l'élément
+/// écarté lorsqu'il est plein.
+///
+/// Le contenu se lit à travers [`as_buffer`](Self::as_buffer), dans l'ordre
+/// d'arrivée.
+pub struct EvictingBuffer<T, E> {
+    items: CircularBuffer<T>,
+    policy: E,
+}
+
+impl<T, E: EvictionPolicy<T>> EvictingBuffer<T, E> {
+    /// Création d'un buffer vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize, policy: E) -> Self {
+        Self {
+            items: CircularBuffer::with_policy(capacity, OverflowPolicy::Reject),
+            policy,
+        }
+    }
+
+    /// Ajout d'un élément ; l'élément écarté, stocké ou entrant, est rendu
+    ///
+    /// Un indice hors limites choisi par la stratégie écarte l'élément entrant.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let mut evicted = None;
+        if self.items.is_full() {
+            match self.policy.victim(&self.items, &item) {
+                Victim::Incoming => return Some(item),
+                Victim::Index(index) => match self.items.remove_at(index) {
+                    Some(old) => evicted = Some(old),
+                    None => return Some(item),
+                },
+            }
+        }
+        // Il reste de la place : l'ajout ne peut pas être refusé
+        let _ = self.items.push(item);
+        evicted
+    }
+
+    /// Retrait de l'élément le plus ancien
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    /// Retourne le buffer sous-jacent, pour en lire le contenu
+    pub fn as_buffer(&self) -> &CircularBuffer<T> {
+        &self.items
+    }
+
+    /// Retourne la stratégie d'éviction
+    pub fn policy(&self) -> &E {
+        &self.policy
+    }
+
+    /// Retourne le nombre d'éléments
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Vide le buffer
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+// Générateur xorshift64* : rapide, sans dépendance et suffisant pour choisir
+// des cases
+#[derive(Debug, Clone)]
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        // L'état ne doit jamais être nul
+        Self(seed | 1)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Tirage dans `0..n`, avec un biais négligeable pour de petits `n`
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn contenu<T: Copy, E: EvictionPolicy<T>>(buffer: &EvictingBuffer<T, E>) -> Vec<T> {
+        buffer.as_buffer().iter().copied().collect()
+    }
+
+    #[test]
+    fn test_strategies() {
+        let mut fifo = EvictingBuffer::new(2, Fifo);
+        let mut lifo = EvictingBuffer::new(2, Lifo);
+        for i in 1..=4 {
+            fifo.push(i);
+            lifo.push(i);
+        }
+        assert_eq!(contenu(&fifo), [3, 4]);
+        assert_eq!(contenu(&lifo), [1, 4]);
+
+        let mut priorite = EvictingBuffer::new(2, LowestBy(|x: &(u8, char)| x.0));
+        priorite.push((5, 'a'));
+        priorite.push((1, 'b'));
+        assert_eq!(priorite.push((0, 'c')), Some((0, 'c')));
+        assert_eq!(priorite.push((1, 'd')), Some((1, 'b')));
+        assert_eq!(contenu(&priorite), [(5, 'a'), (1, 'd')]);
+
+        let mut hasard = EvictingBuffer::new(4, Random::default());
+        for i in 0..100 {
+            hasard.push(i);
+        }
+        assert_eq!(hasard.len(), 4);
+        assert!(hasard.as_buffer().contains(&99));
+    }
+
+    #[test]
+    fn test_strategie_fermeture() {
+        // Garde les éléments pairs en écartant les impairs entrants
+        let mut pairs = EvictingBuffer::new(2, |items: &CircularBuffer<u32>, x: &u32| match items
+            .position(|y| y % 2 == 1)
+        {
+            Some(index) => Victim::Index(index),
+            None if x % 2 == 1 => Victim::Incoming,
+            None => Victim::Index(0),
+        });
+        for i in 0..7 {
+            pairs.push(i);
+        }
+        assert_eq!(contenu(&pairs), [4, 6]);
+    }
+}
@@ -0,0 +1,135 @@
+//! Historique d'états avec annulation et rétablissement.
+
+use crate::CircularBuffer;
+
+/// Historique borné d'états, par exemple pour annuler les modifications d'un
+/// éditeur.
+///
+/// Un curseur désigne l'état courant : [`undo`](Self::undo) le recule vers
+/// les états plus anciens et [`redo`](Self::redo) l'avance à nouveau.
+/// [`record`](Self::record) après une annulation abandonne les états qui
+/// pouvaient être rétablis, comme un éditeur qui repart sur une nouvelle
+/// branche. Une fois plein, l'état le plus ancien est écrasé.
+pub struct HistoryBuffer<T> {
+    states: CircularBuffer<T>,
+    // Nombre d'états jusqu'à l'état courant inclus ; ceux qui suivent peuvent
+    // être rétablis
+    position: usize,
+}
+
+impl<T> HistoryBuffer<T> {
+    /// Création d'un historique vide de `capacity` états
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            states: CircularBuffer::new(capacity),
+            position: 0,
+        }
+    }
+
+    /// Enregistre `state` comme état courant, en abandonnant les états qui
+    /// pouvaient être rétablis
+    pub fn record(&mut self, state: T) {
+        while self.states.len() > self.position {
+            self.states.pop_back();
+        }
+        self.states.push(state);
+        self.position = self.states.len();
+    }
+
+    /// Revient à l'état précédent et le retourne, s'il y en a un
+    pub fn undo(&mut self) -> Option<&T> {
+        if self.position <= 1 {
+            return None;
+        }
+        self.position -= 1;
+        self.current()
+    }
+
+    /// Rétablit l'état suivant et le retourne, s'il y en a un
+    pub fn redo(&mut self) -> Option<&T> {
+        if self.position == self.states.len() {
+            return None;
+        }
+        self.position += 1;
+        self.current()
+    }
+
+    /// Retourne l'état courant
+    pub fn current(&self) -> Option<&T> {
+        self.states.get(self.position.checked_sub(1)?)
+    }
+
+    /// Vérifie si un état précédent est disponible
+    pub fn can_undo(&self) -> bool {
+        self.position > 1
+    }
+
+    /// Vérifie si un état peut être rétabli
+    pub fn can_redo(&self) -> bool {
+        self.position < self.states.len()
+    }
+
+    /// Retourne le nombre d'états gardés, y compris ceux qui peuvent être
+    /// rétablis
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Vérifie si l'historique est vide
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Retourne le nombre maximal d'états
+    pub fn capacity(&self) -> usize {
+        self.states.capacity()
+    }
+
+    /// Oublie tous les états
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.position = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annuler_retablir() {
+        let mut historique = HistoryBuffer::new(10);
+        assert_eq!(historique.undo(), None);
+        historique.record("a");
+        historique.record("ab");
+        historique.record("abc");
+        assert_eq!(historique.undo(), Some(&"ab"));
+        assert_eq!(historique.undo(), Some(&"a"));
+        assert_eq!(historique.undo(), None);
+        assert_eq!(historique.redo(), Some(&"ab"));
+        historique.record("abd");
+        assert!(!historique.can_redo());
+        assert_eq!(historique.redo(), None);
+        assert_eq!(historique.len(), 3);
+        assert_eq!(historique.undo(), Some(&"ab"));
+        assert_eq!(historique.current(), Some(&"ab"));
+    }
+
+    #[test]
+    fn test_ancien_historique_ecrase() {
+        let mut historique = HistoryBuffer::new(2);
+        for etat in 1..=4 {
+            historique.record(etat);
+        }
+        assert_eq!(historique.undo(), Some(&3));
+        assert_eq!(historique.undo(), None);
+        historique.record(5);
+        historique.record(6);
+        assert_eq!(historique.undo(), Some(&5));
+        assert!(!historique.can_undo());
+    }
+}
@@ -0,0 +1,499 @@
+//! Itérateurs sur le contenu d'un [`CircularBuffer`], du plus ancien au plus
+//! récent.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::mem::MaybeUninit;
+use core::slice;
+
+use crate::{CircularBuffer, Storage};
+
+/// Itérateur sur des références aux éléments d'un buffer.
+///
+/// Le contenu logique est parcouru en deux morceaux contigus : de `tail` à la
+/// fin du stockage, puis depuis le début du stockage pour la partie enroulée.
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(front: &'a [T], back: &'a [T]) -> Self {
+        Self {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+// Éléments d'indices logiques `start..end` des deux morceaux `front` et `back`
+pub(crate) fn sub_range<'a, T>(
+    front: &'a [T],
+    back: &'a [T],
+    start: usize,
+    end: usize,
+) -> Iter<'a, T> {
+    let split = front.len();
+    Iter::new(
+        &front[start.min(split)..end.min(split)],
+        &back[start.saturating_sub(split)..end.saturating_sub(split)],
+    )
+}
+
+/// Itérateur sur les fenêtres de `size` éléments consécutifs qui se
+/// chevauchent, créé par [`CircularBuffer::windows`].
+///
+/// Chaque fenêtre est un [`Iter`], le contenu pouvant être réparti sur les
+/// deux morceaux du stockage.
+pub struct Windows<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+    size: usize,
+    index: usize,
+}
+
+impl<'a, T> Windows<'a, T> {
+    pub(crate) fn new(front: &'a [T], back: &'a [T], size: usize) -> Self {
+        assert!(size > 0, "La taille des fenêtres doit être positive.");
+        Self {
+            front,
+            back,
+            size,
+            index: 0,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        (self.front.len() + self.back.len() + 1).saturating_sub(self.index + self.size)
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Iter<'a, T>> {
+        if self.remaining() == 0 {
+            return None;
+        }
+        let window = sub_range(self.front, self.back, self.index, self.index + self.size);
+        self.index += 1;
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T> {}
+
+impl<T> FusedIterator for Windows<'_, T> {}
+
+/// Itérateur sur des morceaux de `size` éléments qui ne se chevauchent pas,
+/// le dernier pouvant être plus court, créé par [`CircularBuffer::chunks`].
+pub struct Chunks<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+    size: usize,
+    index: usize,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    pub(crate) fn new(front: &'a [T], back: &'a [T], size: usize) -> Self {
+        assert!(size > 0, "La taille des morceaux doit être positive.");
+        Self {
+            front,
+            back,
+            size,
+            index: 0,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        let left = self.front.len() + self.back.len() - self.index;
+        left.div_ceil(self.size)
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Iter<'a, T>> {
+        let len = self.front.len() + self.back.len();
+        if self.index == len {
+            return None;
+        }
+        let end = (self.index + self.size).min(len);
+        let chunk = sub_range(self.front, self.back, self.index, end);
+        self.index = end;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl<T> ExactSizeIterator for Chunks<'_, T> {}
+
+impl<T> FusedIterator for Chunks<'_, T> {}
+
+/// Itérateur sur des références mutables aux éléments d'un buffer.
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(front: &'a mut [T], back: &'a mut [T]) -> Self {
+        Self {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Itérateur consommant un buffer.
+pub struct IntoIter<T, S: Storage<T> = Box<[MaybeUninit<T>]>> {
+    buffer: CircularBuffer<T, S>,
+}
+
+impl<T, S: Storage<T>> Iterator for IntoIter<T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len(), Some(self.buffer.len()))
+    }
+}
+
+impl<T, S: Storage<T>> DoubleEndedIterator for IntoIter<T, S> {
+    fn next_back(&mut self) -> Option<T> {
+        self.buffer.pop_back()
+    }
+}
+
+impl<T, S: Storage<T>> ExactSizeIterator for IntoIter<T, S> {}
+
+impl<T, S: Storage<T>> FusedIterator for IntoIter<T, S> {}
+
+/// Itérateur vidant un buffer, créé par [`CircularBuffer::drain`].
+///
+/// Les éléments non parcourus sont libérés lorsque l'itérateur est abandonné.
+pub struct Drain<'a, T, S: Storage<T> = Box<[MaybeUninit<T>]>> {
+    buffer: &'a mut CircularBuffer<T, S>,
+}
+
+impl<'a, T, S: Storage<T>> Drain<'a, T, S> {
+    pub(crate) fn new(buffer: &'a mut CircularBuffer<T, S>) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<T, S: Storage<T>> Iterator for Drain<'_, T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len(), Some(self.buffer.len()))
+    }
+}
+
+impl<T, S: Storage<T>> DoubleEndedIterator for Drain<'_, T, S> {
+    fn next_back(&mut self) -> Option<T> {
+        self.buffer.pop_back()
+    }
+}
+
+impl<T, S: Storage<T>> ExactSizeIterator for Drain<'_, T, S> {}
+
+impl<T, S: Storage<T>> FusedIterator for Drain<'_, T, S> {}
+
+impl<T, S: Storage<T>> Drop for Drain<'_, T, S> {
+    fn drop(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+/// Itérateur retirant les plus anciens éléments tant qu'ils vérifient un
+/// prédicat, créé par [`CircularBuffer::drain_while`].
+///
+/// Abandonné avant la fin, il retire quand même le reste des éléments qui
+/// vérifient le prédicat.
+pub struct DrainWhile<'a, T, F, S = Box<[MaybeUninit<T>]>>
+where
+    F: FnMut(&T) -> bool,
+    S: Storage<T>,
+{
+    buffer: &'a mut CircularBuffer<T, S>,
+    predicate: F,
+    done: bool,
+}
+
+impl<'a, T, F: FnMut(&T) -> bool, S: Storage<T>> DrainWhile<'a, T, F, S> {
+    pub(crate) fn new(buffer: &'a mut CircularBuffer<T, S>, predicate: F) -> Self {
+        Self {
+            buffer,
+            predicate,
+            done: false,
+        }
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool, S: Storage<T>> Iterator for DrainWhile<'_, T, F, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        match self.buffer.peek() {
+            Some(item) if (self.predicate)(item) => self.buffer.pop(),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let max = if self.done { 0 } else { self.buffer.len() };
+        (0, Some(max))
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool, S: Storage<T>> FusedIterator for DrainWhile<'_, T, F, S> {}
+
+impl<T, F: FnMut(&T) -> bool, S: Storage<T>> Drop for DrainWhile<'_, T, F, S> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+impl<T, S: Storage<T>> IntoIterator for CircularBuffer<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T, S>;
+
+    fn into_iter(self) -> IntoIter<T, S> {
+        IntoIter { buffer: self }
+    }
+}
+
+impl<'a, T, S: Storage<T>> IntoIterator for &'a CircularBuffer<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, S: Storage<T>> IntoIterator for &'a mut CircularBuffer<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// La capacité du buffer obtenu est égale au nombre d'éléments collectés
+/// (au moins 1). Voir [`CircularBuffer::from_iter_with_capacity`] pour ne
+/// garder que les derniers éléments d'un itérateur.
+impl<T> FromIterator<T> for CircularBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let items: Vec<T> = iter.into_iter().collect();
+        Self::from_boxed_slice(items.into_boxed_slice())
+    }
+}
+
+/// Les éléments sont ajoutés un par un avec [`CircularBuffer::push`].
+impl<T, S: Storage<T>> Extend<T> for CircularBuffer<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, S: Storage<T>> Extend<&'a T> for CircularBuffer<T, S> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CircularBuffer;
+
+    // Buffer de capacité 4 contenant 3, 4, 5, 6 avec un contenu enroulé
+    fn buffer_enroule() -> CircularBuffer<i32> {
+        let mut buffer = CircularBuffer::new(4);
+        for i in 1..=6 {
+            buffer.push(i);
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_into_iter_reference() {
+        let buffer = buffer_enroule();
+        let mut vus = Vec::new();
+        for x in &buffer {
+            vus.push(*x);
+        }
+        assert_eq!(vus, vec![3, 4, 5, 6]);
+        assert_eq!((&buffer).into_iter().size_hint(), (4, Some(4)));
+    }
+
+    #[test]
+    fn test_into_iter_mutable() {
+        let mut buffer = buffer_enroule();
+        for x in &mut buffer {
+            *x *= 10;
+        }
+        assert_eq!(buffer.pop(), Some(30));
+        assert_eq!(buffer.pop(), Some(40));
+        assert_eq!(buffer.pop(), Some(50));
+        assert_eq!(buffer.pop(), Some(60));
+    }
+
+    #[test]
+    fn test_into_iter_par_valeur() {
+        let buffer = buffer_enroule();
+        let tout: Vec<i32> = buffer.into_iter().collect();
+        assert_eq!(tout, vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_collect() {
+        let buffer: CircularBuffer<i32> = (1..=4).collect();
+        assert_eq!(buffer.capacity(), 4);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        let mut vide: CircularBuffer<i32> = core::iter::empty().collect();
+        assert_eq!(vide.capacity(), 1);
+        vide.push(7);
+        assert_eq!(vide.pop(), Some(7));
+    }
+
+    #[test]
+    fn test_from_iter_with_capacity() {
+        let buffer = CircularBuffer::from_iter_with_capacity(3, 1..=10);
+        assert_eq!(buffer.into_iter().collect::<Vec<_>>(), vec![8, 9, 10]);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend(1..=4);
+        buffer.extend(&[5]);
+        assert_eq!(buffer.into_iter().collect::<Vec<_>>(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut buffer = buffer_enroule();
+        let vides: Vec<i32> = buffer.drain().map(|x| x * 2).collect();
+        assert_eq!(vides, vec![6, 8, 10, 12]);
+        assert!(buffer.is_empty());
+
+        let mut buffer = buffer_enroule();
+        let mut drain = buffer.drain();
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next(), Some(3));
+        assert_eq!(drain.next_back(), Some(6));
+        drop(drain);
+        assert!(buffer.is_empty());
+        buffer.push(7);
+        assert_eq!(buffer.pop(), Some(7));
+    }
+
+    #[test]
+    fn test_iter_rev_et_len() {
+        let mut buffer = buffer_enroule();
+        let recents: Vec<i32> = buffer.iter().rev().copied().collect();
+        assert_eq!(recents, vec![6, 5, 4, 3]);
+
+        let mut iter = buffer.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        if let Some(dernier) = buffer.iter_mut().next_back() {
+            *dernier = 0;
+        }
+        let mut par_valeur = buffer.into_iter();
+        assert_eq!(par_valeur.next_back(), Some(0));
+        assert_eq!(par_valeur.len(), 3);
+        assert_eq!(par_valeur.rev().collect::<Vec<_>>(), vec![5, 4, 3]);
+    }
+}
@@ -0,0 +1,2517 @@
+//! Buffer circulaire générique.
+//!
+//! Un [`CircularBuffer`] conserve au plus `capacity()` éléments : lorsqu'il est
+//! plein, chaque nouvel ajout écrase l'élément le plus ancien. Ce comportement
+//! se règle à la construction avec une [`OverflowPolicy`].
+//!
+//! Un [`CircularBuffer`] peut aussi fonctionner sur un [`Storage`] fourni par
+//! l'appelant : `Vec`, tableau ou tranche empruntée. [`ArrayCircularBuffer`]
+//! est ce même buffer sur un tableau, de capacité fixée à la compilation et
+//! sans allocation.
+//!
+//! Le module [`spsc`] fournit une variante sans verrou pour échanger des
+//! éléments entre un thread producteur et un thread consommateur, et le module
+//! [`mpmc`] une file sans verrou partagée par plusieurs producteurs et
+//! consommateurs.
+//!
+//! # Fonctionnalités
+//!
+//! - `std` (activée par défaut) : affichage sur la sortie standard et
+//!   sauvegarde binaire (`save_to` / `load_from`), [`BlockingCircularBuffer`]
+//!   dont les opérations attendent de la place ou des éléments, le module
+//!   [`broadcast`] qui diffuse chaque élément à plusieurs lecteurs,
+//!   [`PersistentRingBuffer`] stocké dans un fichier, [`ByteRingBuffer`] et
+//!   [`MirroredByteRing`] qui implémentent `io::Read` et `io::Write`,
+//!   [`TimedCircularBuffer`] dont les éléments expirent, [`RateLimiter`] qui
+//!   limite le nombre d'événements par fenêtre, [`RollingOutcomeWindow`]
+//!   qui suit le taux d'échec des derniers appels, le cache [`LruRing`] et
+//!   [`RingSet`] qui garde les derniers éléments distincts. Sans elle, la
+//!   crate est `#![no_std]` et ne dépend que de `alloc`.
+//! - `async` : [`AsyncCircularBuffer`], dont l'ajout et le retrait sont des
+//!   futures réveillées par leur `Waker` (implique `std`).
+//! - `futures` : `Stream` et `Sink` pour les moitiés d'un
+//!   [`AsyncCircularBuffer`] (implique `async`).
+//! - `log` : [`RingLogger`], journal de la crate `log` qui garde les derniers
+//!   messages en mémoire (implique `std`).
+//! - `mirror` : projection double du stockage de [`MirroredByteRing`] sous
+//!   Linux (implique `std`).
+//! - `shm` : module `shm`, buffer d'enregistrements dans un fichier projeté
+//!   en mémoire partagée entre processus (Unix, implique `std`).
+//! - `tokio` : [`AsyncByteRing`], buffer d'octets partagé implémentant
+//!   `AsyncRead` et `AsyncWrite` de `tokio` (implique `std`).
+//! - `tracing` : [`RingLayer`], couche `tracing_subscriber` qui garde les
+//!   derniers événements et spans en mémoire (implique `std`).
+//! - `serde` : sérialisation du contenu et de la capacité avec `serde`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod array;
+#[cfg(feature = "async")]
+mod async_buffer;
+#[cfg(feature = "std")]
+mod binary;
+#[cfg(feature = "std")]
+mod blocking;
+#[cfg(feature = "std")]
+pub mod broadcast;
+mod builder;
+#[cfg(feature = "std")]
+mod bytes;
+mod convert;
+#[cfg(target_has_atomic = "ptr")]
+mod cow;
+mod cursor;
+mod decimate;
+mod error;
+mod eviction;
+mod history;
+mod iter;
+#[cfg(feature = "log")]
+mod logger;
+#[cfg(feature = "std")]
+mod lru;
+#[cfg(feature = "std")]
+mod mirrored;
+#[cfg(target_has_atomic = "ptr")]
+pub mod mpmc;
+#[cfg(feature = "std")]
+mod outcome;
+#[cfg(target_has_atomic = "ptr")]
+mod padded;
+#[cfg(feature = "std")]
+mod persistent;
+mod priority;
+#[cfg(feature = "std")]
+mod rate_limiter;
+mod reservoir;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(all(feature = "shm", unix, target_has_atomic = "64"))]
+pub mod shm;
+mod snapshot;
+#[cfg(target_has_atomic = "ptr")]
+pub mod spsc;
+mod stats;
+mod storage;
+#[cfg(feature = "std")]
+mod timed;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
+mod watermark;
+mod weighted;
+mod window;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use core::ptr;
+
+pub use array::ArrayCircularBuffer;
+#[cfg(feature = "async")]
+pub use async_buffer::{
+    AsyncCircularBuffer, AsyncConsumer, AsyncProducer, PopFuture, PushFuture, RecvFuture,
+    SendFuture,
+};
+#[cfg(feature = "std")]
+pub use binary::BinaryElement;
+#[cfg(feature = "std")]
+pub use blocking::BlockingCircularBuffer;
+pub use builder::CircularBufferBuilder;
+#[cfg(feature = "std")]
+pub use bytes::ByteRingBuffer;
+#[cfg(feature = "tokio")]
+pub use bytes::{AsyncByteRing, Duplex};
+pub use convert::RingCollect;
+#[cfg(target_has_atomic = "ptr")]
+pub use cow::CowCircularBuffer;
+pub use cursor::Cursor;
+pub use decimate::DecimatingBuffer;
+pub use error::CircularBufferError;
+pub use eviction::{EvictingBuffer, EvictionPolicy, Fifo, Lifo, LowestBy, Random, Victim};
+pub use history::HistoryBuffer;
+pub use iter::{Chunks, Drain, DrainWhile, IntoIter, Iter, IterMut, Windows};
+#[cfg(feature = "log")]
+pub use logger::RingLogger;
+#[cfg(feature = "std")]
+pub use lru::{LruRing, RingSet};
+#[cfg(feature = "std")]
+pub use mirrored::MirroredByteRing;
+#[cfg(feature = "std")]
+pub use outcome::RollingOutcomeWindow;
+#[cfg(feature = "std")]
+pub use persistent::PersistentRingBuffer;
+pub use priority::PriorityCircularBuffer;
+#[cfg(feature = "std")]
+pub use rate_limiter::RateLimiter;
+pub use reservoir::ReservoirBuffer;
+pub use snapshot::{FrozenBuffer, Snapshot};
+pub use stats::BufferStats;
+pub use storage::Storage;
+#[cfg(feature = "std")]
+pub use timed::{Clock, ManualClock, SystemClock, TimedCircularBuffer};
+#[cfg(feature = "tracing")]
+pub use tracing_layer::{RingLayer, TraceKind, TraceRecord};
+pub use watermark::Watermark;
+use watermark::Watermarks;
+pub use weighted::{ByteLen, Weigher, WeightedCircularBuffer};
+pub use window::{Ewma, SlidingMean, SlidingMinMax, SlidingSum};
+
+/// Comportement de [`CircularBuffer::push`] lorsque le buffer est plein.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Écrase l'élément le plus ancien (comportement par défaut).
+    #[default]
+    Overwrite,
+    /// Refuse le nouvel élément, qui est rendu à l'appelant.
+    Reject,
+    /// Augmente la capacité du buffer (la double par défaut, voir
+    /// [`CircularBufferBuilder::growth_factor`]).
+    Grow,
+}
+
+/// Buffer circulaire de taille fixe.
+///
+/// Les cases sont stockées sans enveloppe `Option` : seules les `len()` cases
+/// à partir de `start` sont initialisées. Elles sont allouées sur le tas par
+/// défaut ; [`from_storage`](Self::from_storage) accepte tout autre
+/// [`Storage`].
+pub struct CircularBuffer<T, S: Storage<T> = Box<[MaybeUninit<T>]>> {
+    buffer: S,
+    // Positions absolues du prochain élément écrit et du plus ancien élément,
+    // calculées modulo 2^64 (`push_front` peut faire reculer "tail" sous 0) ;
+    // leur différence est le nombre d'éléments
+    head: u64,
+    tail: u64,
+    // Case physique du plus ancien élément, c'est-à-dire de la position "tail"
+    start: usize,
+    // Éléments écrasés depuis le dernier élément lu
+    missed: u64,
+    stats: BufferStats,
+    policy: OverflowPolicy,
+    growth_factor: usize,
+    on_evict: Option<EvictHook<T>>,
+    watermarks: Option<Watermarks>,
+}
+
+// Fermeture appelée sur chaque élément évincé
+struct EvictHook<T>(Box<dyn FnMut(T) + Send>);
+
+// SAFETY: la fermeture n'est appelée qu'à travers `&mut self` ; un accès
+// partagé au buffer ne permet jamais de l'atteindre.
+unsafe impl<T> Sync for EvictHook<T> {}
+
+impl<T> CircularBuffer<T> {
+    /// Création d'un nouveau buffer circulaire
+    ///
+    /// # Panics
+    ///
+    /// Panique si `size` vaut 0 ; [`try_new`](Self::try_new) renvoie une
+    /// erreur à la place.
+    pub fn new(size: usize) -> Self {
+        Self::with_policy(size, OverflowPolicy::Overwrite)
+    }
+
+    /// Création d'un nouveau buffer circulaire, sans paniquer
+    ///
+    /// Renvoie [`CircularBufferError::ZeroCapacity`] si `size` vaut 0.
+    pub fn try_new(size: usize) -> Result<Self, CircularBufferError> {
+        if size == 0 {
+            return Err(CircularBufferError::ZeroCapacity);
+        }
+        Ok(Self::new(size))
+    }
+
+    // Création d'un buffer vide de `size` cases, ou `None` si elles ne
+    // peuvent pas être allouées, pour les capacités lues dans des données
+    // externes
+    #[cfg(any(feature = "std", feature = "serde"))]
+    pub(crate) fn try_allocate(size: usize) -> Option<Self> {
+        let mut slots = Vec::new();
+        slots.try_reserve_exact(size).ok()?;
+        // SAFETY: la réserve couvre `size` cases, qui n'ont pas besoin d'être
+        // initialisées.
+        unsafe { slots.set_len(size) };
+        Some(Self::new_with_storage(slots.into_boxed_slice()))
+    }
+
+    /// Création d'un buffer circulaire avec une politique de débordement donnée
+    ///
+    /// # Panics
+    ///
+    /// Panique si `size` vaut 0.
+    pub fn with_policy(size: usize, policy: OverflowPolicy) -> Self {
+        assert!(size > 0, "La taille du buffer doit être positive.");
+        let mut buffer = Self::new_with_storage(Box::new_uninit_slice(size));
+        buffer.policy = policy;
+        buffer
+    }
+
+    /// Création d'un buffer dont la capacité est `size` arrondie à la
+    /// puissance de deux supérieure
+    ///
+    /// Le passage d'une case à la suivante se fait alors par un masque plutôt
+    /// que par une division.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `size` vaut 0 ou si la puissance de deux dépasse `usize`.
+    pub fn with_pow2_capacity(size: usize) -> Self {
+        Self::new(pow2_capacity(size))
+    }
+
+    /// Création d'un buffer de capacité `size` ne conservant que les `size`
+    /// derniers éléments de `iter`
+    ///
+    /// Panique si `size` vaut 0.
+    pub fn from_iter_with_capacity(size: usize, iter: impl IntoIterator<Item = T>) -> Self {
+        let mut buffer = Self::new(size);
+        for item in iter {
+            buffer.push(item);
+        }
+        buffer
+    }
+
+    // Création d'un buffer plein à partir d'éléments déjà rangés dans l'ordre
+    fn from_boxed_slice(items: Box<[T]>) -> Self {
+        if items.is_empty() {
+            return Self::new(1);
+        }
+        let count = items.len() as u64;
+        // SAFETY: `MaybeUninit<T>` a la même disposition mémoire que `T`.
+        let buffer = unsafe { Box::from_raw(Box::into_raw(items) as *mut [MaybeUninit<T>]) };
+        let mut buffer = Self::new_with_storage(buffer);
+        buffer.head = count;
+        buffer
+    }
+
+    /// Retourne un constructeur pour régler toutes les options du buffer
+    pub fn builder() -> CircularBufferBuilder<T> {
+        CircularBufferBuilder::new()
+    }
+
+    /// Coupe le buffer en deux à l'indice logique `at` : les éléments à partir
+    /// de `at` sont déplacés, dans l'ordre, vers un nouveau buffer de même
+    /// capacité et de même politique
+    ///
+    /// # Panics
+    ///
+    /// Panique si `at` dépasse le nombre d'éléments.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "{}",
+            CircularBufferError::IndexOutOfBounds { index: at, len }
+        );
+        let mut other = Self::with_policy(self.capacity(), self.policy);
+        other.growth_factor = self.growth_factor;
+        for _ in at..len {
+            if let Some(item) = self.pop_newest() {
+                drop(other.push_front(item));
+            }
+        }
+        self.check_watermarks();
+        other
+    }
+}
+
+impl<T, S: Storage<T>> CircularBuffer<T, S> {
+    /// Création d'un buffer sur un stockage fourni par l'appelant, par exemple
+    /// un tableau ou une tranche empruntée ; sa longueur donne la capacité
+    ///
+    /// Le buffer écrase le plus ancien élément lorsqu'il est plein. Sa
+    /// capacité ne peut changer que si le stockage sait s'allouer.
+    ///
+    /// # Panics
+    ///
+    /// Panique si le stockage n'a aucune case.
+    pub fn from_storage(storage: S) -> Self {
+        assert!(
+            !storage.slots().is_empty(),
+            "La taille du buffer doit être positive."
+        );
+        Self::new_with_storage(storage)
+    }
+
+    // Création d'un buffer vide sur un stockage existant
+    const fn new_with_storage(buffer: S) -> Self {
+        Self {
+            buffer,
+            head: 0,
+            tail: 0,
+            start: 0,
+            missed: 0,
+            stats: BufferStats::ZERO,
+            policy: OverflowPolicy::Overwrite,
+            growth_factor: 2,
+            on_evict: None,
+            watermarks: None,
+        }
+    }
+
+    /// Enregistre une fermeture appelée sur chaque élément évincé : écrasé par
+    /// un `push` sur un buffer plein ou abandonné par un `resize` réducteur
+    ///
+    /// Les éléments remis à la fermeture ne sont plus rendus par `push`.
+    pub fn on_evict(mut self, f: impl FnMut(T) + Send + 'static) -> Self {
+        self.on_evict = Some(EvictHook(Box::new(f)));
+        self
+    }
+
+    /// Enregistre une fermeture appelée lorsque le nombre d'éléments atteint
+    /// `high`, puis lorsqu'il redescend à `low`
+    ///
+    /// Chaque seuil n'est signalé qu'une fois par franchissement : après
+    /// [`Watermark::High`], le prochain signal est forcément
+    /// [`Watermark::Low`]. Cela permet de ralentir un producteur avant que le
+    /// buffer ne déborde.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `low` n'est pas inférieur à `high`.
+    pub fn on_watermark(
+        mut self,
+        high: usize,
+        low: usize,
+        f: impl FnMut(Watermark) + Send + 'static,
+    ) -> Self {
+        self.watermarks = Some(Watermarks::new(high, low, Box::new(f)));
+        self.check_watermarks();
+        self
+    }
+
+    /// Retourne la politique appliquée lorsque le buffer est plein
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Ajout d'un élément au buffer
+    ///
+    /// Si le buffer est plein, la [`OverflowPolicy`] décide : la capacité est
+    /// doublée, ou l'élément qui ne trouve pas sa place est rendu dans `Some`
+    /// (le plus ancien s'il est écrasé, le nouveau s'il est refusé).
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let mut evicted = None;
+        if self.is_full() {
+            match self.policy {
+                // Retirer le plus ancien pour faire de la place
+                OverflowPolicy::Overwrite => {
+                    evicted = self.pop_oldest().and_then(|old| self.evict(old));
+                    self.missed += 1;
+                    self.stats.overwrites += 1;
+                }
+                OverflowPolicy::Reject => {
+                    self.stats.rejected += 1;
+                    return Some(item);
+                }
+                OverflowPolicy::Grow => self.relocate(self.capacity() * self.growth_factor),
+            }
+        }
+        self.write_head(item);
+        self.record_pushes(1);
+        evicted
+    }
+
+    /// Fusionne `item` dans le plus récent élément, ou l'ajoute comme
+    /// [`push`](Self::push) si `merge` le refuse
+    ///
+    /// `merge` reçoit le plus récent élément et `item`, et rend `item` dans
+    /// `Err` s'il ne doit pas être fusionné. Une fusion n'occupe pas de case
+    /// et n'écrase rien.
+    pub fn push_or_merge(
+        &mut self,
+        item: T,
+        merge: impl FnOnce(&mut T, T) -> Result<(), T>,
+    ) -> Option<T> {
+        let item = match self.back_mut() {
+            Some(last) => match merge(last, item) {
+                Ok(()) => return None,
+                Err(item) => item,
+            },
+            None => item,
+        };
+        self.push(item)
+    }
+
+    /// Ajout de tous les éléments de `items`, ou d'aucun
+    ///
+    /// Avec la politique `Reject`, le lot doit tenir dans la place libre ;
+    /// avec `Overwrite`, il doit tenir dans la capacité, quitte à écraser les
+    /// plus anciens ; avec `Grow`, il est toujours accepté. Sinon, le buffer
+    /// n'est pas modifié et [`CircularBufferError::NotEnoughSpace`] est
+    /// retourné.
+    pub fn push_all(
+        &mut self,
+        items: impl IntoIterator<Item = T>,
+    ) -> Result<(), CircularBufferError> {
+        let items: Vec<T> = items.into_iter().collect();
+        let available = match self.policy {
+            OverflowPolicy::Reject => self.capacity() - self.len(),
+            OverflowPolicy::Overwrite => self.capacity(),
+            OverflowPolicy::Grow => usize::MAX,
+        };
+        if items.len() > available {
+            self.stats.rejected += items.len() as u64;
+            return Err(CircularBufferError::NotEnoughSpace {
+                needed: items.len(),
+                available,
+            });
+        }
+        for item in items {
+            drop(self.push(item));
+        }
+        Ok(())
+    }
+
+    /// Ajout d'un élément sans écraser : si le buffer est plein, l'élément est
+    /// rendu à l'appelant dans `Err`, quelle que soit la politique
+    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            self.stats.rejected += 1;
+            return Err(item);
+        }
+        self.write_head(item);
+        self.record_pushes(1);
+        Ok(())
+    }
+
+    /// Retrait de l'élément le plus ancien
+    ///
+    /// Remet à zéro le compteur [`missed`](Self::missed), comme tout retrait
+    /// d'un élément lu.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.pop_oldest()?;
+        self.record_pops(1);
+        Some(item)
+    }
+
+    /// Retrait des `n` plus anciens éléments, ou de tous s'il y en a moins
+    pub fn pop_up_to(&mut self, n: usize) -> Vec<T> {
+        let n = n.min(self.len());
+        (0..n).filter_map(|_| self.pop()).collect()
+    }
+
+    /// Retrait des `n` plus anciens éléments d'un bloc : si le buffer en
+    /// contient moins, rien n'est retiré et `None` est retourné
+    pub fn pop_exact(&mut self, n: usize) -> Option<Vec<T>> {
+        if self.len() < n {
+            return None;
+        }
+        Some(self.pop_up_to(n))
+    }
+
+    /// Nombre d'éléments écrasés par un ajout sur un buffer plein depuis le
+    /// dernier élément lu
+    ///
+    /// Toute méthode qui retire un élément pour le rendre à l'appelant
+    /// ([`pop`](Self::pop), [`pop_back`](Self::pop_back), [`drain`](Self::drain),
+    /// [`pop_into`](Self::pop_into), [`remove`](Self::remove)…) le remet à
+    /// zéro. Un consommateur lent sait ainsi combien d'éléments il a perdus
+    /// avec la politique `Overwrite`.
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    // Retrait du plus ancien, sans compter comme une lecture
+    fn pop_oldest(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None; // Rien à retirer si le buffer est vide
+        }
+        // SAFETY: la case "start" est initialisée et on la considère libre juste après.
+        let item = unsafe { self.buffer.slots()[self.start].assume_init_read() };
+        self.start = self.wrap(self.start + 1); // Avancer "tail"
+        self.tail = self.tail.wrapping_add(1);
+        Some(item)
+    }
+
+    /// Ajout d'un élément avant le plus ancien
+    ///
+    /// Symétrique de [`push`](Self::push) : si le buffer est plein avec la
+    /// politique `Overwrite`, c'est le plus récent qui est écrasé et rendu.
+    pub fn push_front(&mut self, item: T) -> Option<T> {
+        let mut evicted = None;
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Overwrite => {
+                    evicted = self.pop_newest().and_then(|old| self.evict(old));
+                    self.missed += 1;
+                    self.stats.overwrites += 1;
+                }
+                OverflowPolicy::Reject => {
+                    self.stats.rejected += 1;
+                    return Some(item);
+                }
+                OverflowPolicy::Grow => self.relocate(self.capacity() * self.growth_factor),
+            }
+        }
+        self.start = self.wrap(self.start + self.capacity() - 1); // Reculer "tail"
+        self.buffer.slots_mut()[self.start].write(item);
+        self.tail = self.tail.wrapping_sub(1);
+        self.record_pushes(1);
+        evicted
+    }
+
+    /// Retrait de l'élément le plus récent
+    pub fn pop_back(&mut self) -> Option<T> {
+        let item = self.pop_newest()?;
+        self.record_pops(1);
+        Some(item)
+    }
+
+    // Retrait du plus récent, sans compter comme une lecture
+    fn pop_newest(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.head = self.head.wrapping_sub(1); // Reculer "head"
+        let index = self.head_index();
+        // SAFETY: la case précédant "head" est initialisée car le buffer n'est
+        // pas vide.
+        let item = unsafe { self.buffer.slots()[index].assume_init_read() };
+        Some(item)
+    }
+
+    /// Retrait de l'élément d'indice logique `index`, 0 étant le plus ancien
+    ///
+    /// Les éléments du côté le plus court se décalent d'une case pour combler
+    /// le vide.
+    pub fn remove(&mut self, index: usize) -> Result<T, CircularBufferError> {
+        let len = self.len();
+        let item = self
+            .remove_at(index)
+            .ok_or(CircularBufferError::IndexOutOfBounds { index, len })?;
+        self.record_pops(1);
+        Ok(item)
+    }
+
+    /// Insertion d'un élément à l'indice logique `index`, entre les éléments
+    /// `index - 1` et `index` actuels
+    ///
+    /// Les éléments du côté le plus court se décalent d'une case. L'élément
+    /// est rendu dans `Err` si `index` dépasse le nombre d'éléments ou si le
+    /// buffer est plein, quelle que soit la politique.
+    pub fn insert(&mut self, index: usize, item: T) -> Result<(), T> {
+        let len = self.len();
+        if index > len {
+            return Err(item);
+        }
+        if self.is_full() {
+            self.stats.rejected += 1;
+            return Err(item);
+        }
+        if index < len - index {
+            // Ajouter devant le plus ancien puis avancer jusqu'à sa place
+            self.start = self.wrap(self.start + self.capacity() - 1);
+            self.buffer.slots_mut()[self.start].write(item);
+            self.tail = self.tail.wrapping_sub(1);
+            for i in 0..index {
+                let (a, b) = (self.wrap(self.start + i), self.wrap(self.start + i + 1));
+                self.buffer.slots_mut().swap(a, b);
+            }
+        } else {
+            // Ajouter derrière le plus récent puis reculer jusqu'à sa place
+            self.write_head(item);
+            for i in (index..len).rev() {
+                let (a, b) = (self.wrap(self.start + i), self.wrap(self.start + i + 1));
+                self.buffer.slots_mut().swap(a, b);
+            }
+        }
+        self.record_pushes(1);
+        Ok(())
+    }
+
+    /// Échange les éléments d'indices logiques `i` et `j`
+    ///
+    /// # Panics
+    ///
+    /// Panique si l'un des indices dépasse le nombre d'éléments.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let len = self.len();
+        for index in [i, j] {
+            assert!(
+                index < len,
+                "{}",
+                CircularBufferError::IndexOutOfBounds { index, len }
+            );
+        }
+        let (a, b) = (self.wrap(self.start + i), self.wrap(self.start + j));
+        self.buffer.slots_mut().swap(a, b);
+    }
+
+    /// Retrait de l'élément d'indice logique `index`, remplacé par le plus
+    /// ancien ; l'ordre n'est pas conservé, mais rien d'autre ne bouge
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.swap(index, 0);
+        let item = self.pop_oldest()?;
+        self.record_pops(1);
+        Some(item)
+    }
+
+    /// Retrait de l'élément d'indice logique `index`, remplacé par le plus
+    /// récent ; l'ordre n'est pas conservé, mais rien d'autre ne bouge
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        let last = self.len().checked_sub(1)?;
+        if index > last {
+            return None;
+        }
+        self.swap(index, last);
+        self.pop_back()
+    }
+
+    // Retrait de l'élément d'indice logique `index`, sans compter comme une
+    // lecture ; les éléments du côté le plus court se décalent d'une case
+    pub(crate) fn remove_at(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        let mut slot = self.wrap(self.start + index);
+        // SAFETY: la case est occupée ; elle est ensuite repoussée jusqu'à
+        // "start" ou jusqu'à "head", qui devient libre.
+        let item = unsafe { self.buffer.slots()[slot].assume_init_read() };
+        if index < len - 1 - index {
+            for _ in 0..index {
+                let prev = self.wrap(slot + self.capacity() - 1);
+                self.buffer.slots_mut().swap(slot, prev);
+                slot = prev;
+            }
+            self.start = self.wrap(self.start + 1);
+            self.tail = self.tail.wrapping_add(1);
+        } else {
+            for _ in index + 1..len {
+                let next = self.wrap(slot + 1);
+                self.buffer.slots_mut().swap(slot, next);
+                slot = next;
+            }
+            self.head = self.head.wrapping_sub(1);
+        }
+        Some(item)
+    }
+
+    /// Ajout d'un élément en retournant son numéro de séquence : sa position
+    /// absolue, qui ne change plus tant qu'il reste dans le buffer
+    ///
+    /// L'élément est rendu dans `Err` si le buffer est plein avec la politique
+    /// `Reject` ; un élément écrasé est remis à la fermeture d'éviction ou
+    /// libéré.
+    pub fn push_seq(&mut self, item: T) -> Result<u64, T> {
+        if self.is_full() && self.policy == OverflowPolicy::Reject {
+            self.stats.rejected += 1;
+            return Err(item);
+        }
+        drop(self.push(item));
+        Ok(self.head.wrapping_sub(1))
+    }
+
+    /// Retrait de l'élément le plus ancien, accompagné de son numéro de
+    /// séquence
+    pub fn pop_seq(&mut self) -> Option<(u64, T)> {
+        let seq = self.tail;
+        self.pop().map(|item| (seq, item))
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.head == self.tail
+    }
+
+    /// Retourne la taille actuelle du buffer
+    pub fn len(&self) -> usize {
+        self.head.wrapping_sub(self.tail) as usize
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.buffer.slots().len()
+    }
+
+    /// Réduit la capacité du buffer pour qu'elle corresponde à sa taille utilisée
+    ///
+    /// La capacité ne descend jamais en dessous de 1. Sans effet si le
+    /// stockage ne sait pas s'allouer.
+    pub fn shrink_to_fit(&mut self) {
+        if self.len() < self.capacity() {
+            if let Some(new_buffer) = S::allocate(self.len().max(1)) {
+                self.relocate_into(new_buffer);
+            }
+        }
+    }
+
+    /// Redimensionne le buffer circulaire en conservant les éléments dans l'ordre
+    ///
+    /// Si `new_size` est inférieur à `len()`, seuls les `new_size` éléments les
+    /// plus récents sont conservés, comme si les plus anciens avaient été
+    /// écrasés ; ils sont remis à la fermeture d'éviction s'il y en a une.
+    pub fn resize(&mut self, new_size: usize) -> Result<(), CircularBufferError> {
+        self.resize_discarding(new_size, Self::pop_oldest)
+    }
+
+    /// Comme [`resize`](Self::resize), mais conserve les `new_size` éléments
+    /// les plus anciens lorsque le buffer rétrécit
+    pub fn resize_keep_oldest(&mut self, new_size: usize) -> Result<(), CircularBufferError> {
+        self.resize_discarding(new_size, Self::pop_newest)
+    }
+
+    // Redimensionne en retirant avec `discard` les éléments qui ne tiennent plus
+    fn resize_discarding(
+        &mut self,
+        new_size: usize,
+        discard: fn(&mut Self) -> Option<T>,
+    ) -> Result<(), CircularBufferError> {
+        if new_size == 0 {
+            return Err(CircularBufferError::ZeroCapacity);
+        }
+        let new_buffer = S::allocate(new_size).ok_or(CircularBufferError::FixedCapacity)?;
+
+        while self.len() > new_size {
+            if let Some(item) = discard(self) {
+                drop(self.evict(item));
+            }
+        }
+        self.relocate_into(new_buffer);
+        self.check_watermarks();
+        Ok(())
+    }
+
+    /// Ne garde que les `n` éléments les plus récents et libère les autres
+    pub fn truncate_front(&mut self, n: usize) {
+        while self.len() > n {
+            drop(self.pop_oldest());
+        }
+        self.check_watermarks();
+    }
+
+    /// Ne garde que les `n` éléments les plus anciens et libère les autres
+    pub fn truncate_back(&mut self, n: usize) {
+        while self.len() > n {
+            drop(self.pop_newest());
+        }
+        self.check_watermarks();
+    }
+
+    /// Fait tourner le contenu logique de `k` positions vers la gauche : le
+    /// `k`-ième élément devient le plus ancien
+    ///
+    /// Un buffer plein tourne sans déplacer d'élément ; sinon, au plus
+    /// `min(k, len() - k)` éléments changent de case.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `k` dépasse le nombre d'éléments.
+    pub fn rotate_left(&mut self, k: usize) {
+        let len = self.len();
+        assert!(
+            k <= len,
+            "La rotation ({}) dépasse le nombre d'éléments ({}).",
+            k,
+            len
+        );
+        if self.is_full() {
+            self.start = self.wrap(self.start + k);
+        } else if k <= len - k {
+            self.move_front_to_back(k);
+        } else {
+            self.move_back_to_front(len - k);
+        }
+    }
+
+    /// Fait tourner le contenu logique de `k` positions vers la droite : les
+    /// `k` plus récents éléments deviennent les plus anciens
+    ///
+    /// # Panics
+    ///
+    /// Panique si `k` dépasse le nombre d'éléments.
+    pub fn rotate_right(&mut self, k: usize) {
+        let len = self.len();
+        assert!(
+            k <= len,
+            "La rotation ({}) dépasse le nombre d'éléments ({}).",
+            k,
+            len
+        );
+        self.rotate_left(len - k);
+    }
+
+    // Déplace les `k` plus anciens derrière le plus récent ; le buffer ne doit
+    // pas être plein
+    fn move_front_to_back(&mut self, k: usize) {
+        for _ in 0..k {
+            let head = self.head_index();
+            // SAFETY: la case "start" est occupée et la case "head" libre ;
+            // l'élément passe de l'une à l'autre.
+            let item = unsafe { self.buffer.slots()[self.start].assume_init_read() };
+            self.buffer.slots_mut()[head].write(item);
+            self.start = self.wrap(self.start + 1);
+        }
+    }
+
+    // Déplace les `k` plus récents devant le plus ancien ; le buffer ne doit
+    // pas être plein
+    fn move_back_to_front(&mut self, k: usize) {
+        for _ in 0..k {
+            let last = self.wrap(self.start + self.len() - 1);
+            let front = self.wrap(self.start + self.capacity() - 1);
+            // SAFETY: la case "last" est occupée et la case avant "start"
+            // libre ; l'élément passe de l'une à l'autre.
+            let item = unsafe { self.buffer.slots()[last].assume_init_read() };
+            self.buffer.slots_mut()[front].write(item);
+            self.start = front;
+        }
+    }
+
+    /// Retourne une référence au prochain élément à être retiré sans le supprimer
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            // SAFETY: la case "start" est initialisée tant que le buffer n'est pas vide.
+            Some(unsafe { self.buffer.slots()[self.start].assume_init_ref() })
+        }
+    }
+
+    /// Retourne le plus ancien élément (le même que [`peek`](Self::peek))
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Retourne le plus ancien élément, modifiable
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Retourne le dernier élément ajouté
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Retourne le dernier élément ajouté, modifiable
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().checked_sub(1)?)
+    }
+
+    /// Retourne l'élément d'indice logique `index` (0 pour le plus ancien)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        let index = self.wrap(self.start + index);
+        // SAFETY: les `len()` cases à partir de "start" sont initialisées.
+        Some(unsafe { self.buffer.slots()[index].assume_init_ref() })
+    }
+
+    /// Version mutable de [`get`](Self::get)
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        let index = self.wrap(self.start + index);
+        // SAFETY: les `len()` cases à partir de "start" sont initialisées.
+        Some(unsafe { self.buffer.slots_mut()[index].assume_init_mut() })
+    }
+
+    /// Vide complètement le buffer, sans réallouer son stockage
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        // Vider avant de libérer : si un `drop` panique, aucun élément ne
+        // pourra être libéré une seconde fois. Les positions continuent de
+        // croître, seule la case de départ revient au début du stockage.
+        self.tail = self.head;
+        self.start = 0;
+        // SAFETY: ces tranches couvraient les cases occupées, désormais
+        // considérées libres.
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+        self.check_watermarks();
+    }
+
+    /// Vide le buffer en remettant chaque élément à `f`, du plus ancien au
+    /// plus récent
+    pub fn clear_with(&mut self, mut f: impl FnMut(T)) {
+        while let Some(item) = self.pop() {
+            f(item);
+        }
+        self.start = 0;
+    }
+
+    /// Ne conserve que les éléments pour lesquels `f` renvoie `true`, dans
+    /// leur ordre d'origine et sans réallouer
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        // Chaque élément est retiré en tête puis remis en queue s'il est gardé :
+        // après `len()` tours, les survivants sont à nouveau dans l'ordre.
+        for _ in 0..self.len() {
+            if let Some(item) = self.pop_oldest() {
+                if f(&item) {
+                    self.write_head(item);
+                }
+            }
+        }
+        self.check_watermarks();
+    }
+
+    /// Déplace tous les éléments de `other` à la suite de ceux du buffer
+    ///
+    /// La politique du buffer s'applique à chaque élément : avec `Overwrite`,
+    /// les plus anciens sont écrasés ; avec `Reject`, les éléments qui ne
+    /// tiennent pas restent dans `other`.
+    pub fn append(&mut self, other: &mut Self) {
+        while !(self.is_full() && self.policy == OverflowPolicy::Reject) {
+            let Some(item) = other.pop() else {
+                break;
+            };
+            drop(self.push(item));
+        }
+    }
+
+    /// Retourne un curseur de lecture placé sur le plus ancien élément
+    pub fn cursor(&self) -> Cursor {
+        Cursor::new(self.tail)
+    }
+
+    /// Retire tous les éléments, du plus ancien au plus récent, au fil de
+    /// l'itération ; le buffer est vide une fois l'itérateur abandonné
+    pub fn drain(&mut self) -> Drain<'_, T, S> {
+        Drain::new(self)
+    }
+
+    /// Retire les plus anciens éléments tant que `f` renvoie `true` pour eux,
+    /// au fil de l'itération
+    ///
+    /// Le premier élément refusé et tous les suivants restent dans le buffer.
+    pub fn drain_while<F: FnMut(&T) -> bool>(&mut self, f: F) -> DrainWhile<'_, T, F, S> {
+        DrainWhile::new(self, f)
+    }
+
+    /// Libère les plus anciens éléments tant que `f` renvoie `true` pour eux,
+    /// et retourne leur nombre
+    pub fn pop_while(&mut self, f: impl FnMut(&T) -> bool) -> usize {
+        self.drain_while(f).count()
+    }
+
+    /// Permet de traverser le buffer, du plus ancien au plus récent
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter::new(front, back)
+    }
+
+    /// Parcourt les `n` éléments les plus anciens, ou tous s'il y en a moins,
+    /// sans les retirer
+    pub fn first_n(&self, n: usize) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        iter::sub_range(front, back, 0, n.min(self.len()))
+    }
+
+    /// Parcourt les `n` éléments les plus récents, ou tous s'il y en a moins,
+    /// du plus ancien au plus récent et sans les retirer
+    pub fn last_n(&self, n: usize) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        let len = self.len();
+        iter::sub_range(front, back, len - n.min(len), len)
+    }
+
+    /// Parcourt toutes les fenêtres de `size` éléments consécutifs, du plus
+    /// ancien au plus récent, sans copier le contenu
+    ///
+    /// Pour travailler sur des tranches, voir
+    /// [`make_contiguous`](Self::make_contiguous).
+    ///
+    /// # Panics
+    ///
+    /// Panique si `size` vaut 0.
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        let (front, back) = self.as_slices();
+        Windows::new(front, back, size)
+    }
+
+    /// Parcourt le contenu par morceaux de `size` éléments, le dernier pouvant
+    /// être plus court
+    ///
+    /// # Panics
+    ///
+    /// Panique si `size` vaut 0.
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T> {
+        let (front, back) = self.as_slices();
+        Chunks::new(front, back, size)
+    }
+
+    /// Permet de modifier les éléments sur place, du plus ancien au plus récent
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut::new(front, back)
+    }
+
+    /// Retourne l'indice logique du premier élément, du plus ancien au plus
+    /// récent, pour lequel `f` renvoie `true`
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.iter().position(f)
+    }
+
+    /// Retourne le premier élément, du plus ancien au plus récent, pour lequel
+    /// `f` renvoie `true`
+    pub fn find(&self, mut f: impl FnMut(&T) -> bool) -> Option<&T> {
+        self.iter().find(|item| f(item))
+    }
+
+    /// Recherche dichotomique dans un buffer trié selon `f`, du plus ancien au
+    /// plus récent
+    ///
+    /// Même contrat que [`slice::binary_search_by`] : `Ok` donne l'indice
+    /// logique d'un élément correspondant, `Err` l'indice où l'insérer.
+    pub fn binary_search_by(&self, mut f: impl FnMut(&T) -> Ordering) -> Result<usize, usize> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match f(&self[mid]) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+
+    /// Recherche dichotomique selon la clé extraite par `f`
+    pub fn binary_search_by_key<K: Ord>(
+        &self,
+        key: &K,
+        mut f: impl FnMut(&T) -> K,
+    ) -> Result<usize, usize> {
+        self.binary_search_by(|item| f(item).cmp(key))
+    }
+
+    /// Retourne l'indice logique du premier élément pour lequel `pred` renvoie
+    /// `false`, le buffer étant partitionné (les `true` d'abord)
+    pub fn partition_point(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.binary_search_by(|item| {
+            if pred(item) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|index| index)
+    }
+
+    /// Parcourt les éléments dans l'ordre de leurs cases de stockage, sans
+    /// tenir compte de la position de "tail"
+    pub fn iter_unordered(&self) -> impl Iterator<Item = &T> {
+        self.buffer
+            .slots()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_occupied(*i))
+            // SAFETY: seules les cases occupées sont lues.
+            .map(|(_, slot)| unsafe { slot.assume_init_ref() })
+    }
+
+    // Remet `item` à la fermeture d'éviction, ou le rend s'il n'y en a pas
+    fn evict(&mut self, item: T) -> Option<T> {
+        match &mut self.on_evict {
+            Some(EvictHook(f)) => {
+                f(item);
+                None
+            }
+            None => Some(item),
+        }
+    }
+
+    /// Retourne les compteurs d'activité du buffer
+    pub fn stats(&self) -> BufferStats {
+        BufferStats {
+            len: self.len(),
+            capacity: self.capacity(),
+            ..self.stats
+        }
+    }
+
+    /// Remet les compteurs d'activité à zéro ; le plus haut niveau repart de
+    /// la taille actuelle
+    pub fn reset_stats(&mut self) {
+        self.stats = BufferStats {
+            high_water_mark: self.len(),
+            ..BufferStats::default()
+        };
+    }
+
+    // Compte `n` éléments ajoutés et met à jour le plus haut niveau atteint
+    fn record_pushes(&mut self, n: usize) {
+        self.stats.pushes += n as u64;
+        self.stats.high_water_mark = self.stats.high_water_mark.max(self.len());
+        self.check_watermarks();
+    }
+
+    // Compte `n` éléments lus, ce qui remet à zéro les éléments manqués
+    fn record_pops(&mut self, n: usize) {
+        if n > 0 {
+            self.missed = 0;
+        }
+        self.stats.pops += n as u64;
+        self.check_watermarks();
+    }
+
+    // Signale un seuil de remplissage franchi depuis le dernier appel
+    fn check_watermarks(&mut self) {
+        let len = self.len();
+        if let Some(watermarks) = &mut self.watermarks {
+            watermarks.update(len);
+        }
+    }
+
+    // Écrit `item` à "head" ; le buffer ne doit pas être plein
+    fn write_head(&mut self, item: T) {
+        debug_assert!(!self.is_full());
+        let index = self.head_index();
+        self.buffer.slots_mut()[index].write(item); // Ajouter l'élément à "head"
+        self.head = self.head.wrapping_add(1); // Avancer "head"
+    }
+
+    // Case physique correspondant à la position "head"
+    #[inline]
+    fn head_index(&self) -> usize {
+        self.wrap(self.start + self.len())
+    }
+
+    /// Retourne le contenu sous forme de deux tranches contiguës : la première
+    /// commence au plus ancien élément, la seconde contient la partie enroulée
+    /// au début du stockage (vide si le contenu ne fait pas le tour)
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let len = self.len();
+        let (wrapped, from_tail) = self.buffer.slots().split_at(self.start);
+        let first = len.min(from_tail.len());
+        // SAFETY: ces deux plages couvrent exactement les `len` cases occupées.
+        unsafe {
+            (
+                assume_init_slice(&from_tail[..first]),
+                assume_init_slice(&wrapped[..len - first]),
+            )
+        }
+    }
+
+    /// Réorganise le stockage pour que tous les éléments soient contigus, puis
+    /// retourne-les dans une seule tranche, du plus ancien au plus récent
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.start + self.len() > self.capacity() {
+            // Le contenu fait le tour : ramener "tail" au début du stockage
+            self.buffer.slots_mut().rotate_left(self.start);
+            self.start = 0;
+        }
+        let (contents, wrapped) = self.as_mut_slices();
+        debug_assert!(wrapped.is_empty());
+        contents
+    }
+
+    /// Trie les éléments présents, du plus ancien au plus récent, en
+    /// conservant l'ordre des éléments égaux
+    pub fn sort_by(&mut self, f: impl FnMut(&T, &T) -> Ordering) {
+        self.make_contiguous().sort_by(f);
+    }
+
+    /// Comme [`sort_by`](Self::sort_by), sans garantie sur l'ordre des
+    /// éléments égaux mais sans allocation
+    pub fn sort_unstable_by(&mut self, f: impl FnMut(&T, &T) -> Ordering) {
+        self.make_contiguous().sort_unstable_by(f);
+    }
+
+    /// Version mutable de [`as_slices`](Self::as_slices)
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let len = self.len();
+        let (wrapped, from_tail) = self.buffer.slots_mut().split_at_mut(self.start);
+        let first = len.min(from_tail.len());
+        // SAFETY: ces deux plages couvrent exactement les `len` cases occupées.
+        unsafe {
+            (
+                assume_init_slice_mut(&mut from_tail[..first]),
+                assume_init_slice_mut(&mut wrapped[..len - first]),
+            )
+        }
+    }
+
+    // Ramène dans le stockage une position inférieure à deux fois la capacité ;
+    // un masque suffit lorsque la capacité est une puissance de deux, sinon
+    // une soustraction évite la division d'un `%`
+    #[inline]
+    fn wrap(&self, index: usize) -> usize {
+        let cap = self.capacity();
+        debug_assert!(index < 2 * cap);
+        if cap.is_power_of_two() {
+            pow2_slot(index, cap)
+        } else if index >= cap {
+            index - cap
+        } else {
+            index
+        }
+    }
+
+    // Indique si la case physique `index` contient un élément
+    fn is_occupied(&self, index: usize) -> bool {
+        self.wrap(index + self.capacity() - self.start) < self.len()
+    }
+
+    // Déplace les éléments, dans l'ordre, vers un nouveau stockage de taille
+    // `new_size` (qui doit être au moins égale à `len()`) ; les positions
+    // absolues ne changent pas
+    fn relocate(&mut self, new_size: usize) {
+        let new_buffer = S::allocate(new_size)
+            .unwrap_or_else(|| panic!("{}", CircularBufferError::FixedCapacity));
+        self.relocate_into(new_buffer);
+    }
+
+    // Déplace les éléments, dans l'ordre, vers le stockage `new_buffer`
+    fn relocate_into(&mut self, mut new_buffer: S) {
+        debug_assert!(new_buffer.slots().len() >= self.len());
+        let len = self.len();
+        for (i, slot) in new_buffer.slots_mut().iter_mut().enumerate().take(len) {
+            let index = self.wrap(self.start + i);
+            // SAFETY: la case est occupée ; l'ancien stockage est abandonné sans
+            // relire ses éléments, la valeur est donc déplacée et non dupliquée.
+            slot.write(unsafe { self.buffer.slots()[index].assume_init_read() });
+        }
+        self.buffer = new_buffer;
+        self.start = 0;
+    }
+}
+
+// SAFETY: toutes les cases de `slots` doivent être initialisées.
+unsafe fn assume_init_slice<T>(slots: &[MaybeUninit<T>]) -> &[T] {
+    unsafe { &*(slots as *const [MaybeUninit<T>] as *const [T]) }
+}
+
+// SAFETY: toutes les cases de `slots` doivent être initialisées.
+unsafe fn assume_init_slice_mut<T>(slots: &mut [MaybeUninit<T>]) -> &mut [T] {
+    unsafe { &mut *(slots as *mut [MaybeUninit<T>] as *mut [T]) }
+}
+
+// Capacité `size` arrondie à la puissance de deux supérieure, pour que
+// `pow2_slot` puisse ramener une position dans le stockage ; panique si
+// `size` vaut 0 ou si la puissance de deux dépasse `usize`
+pub(crate) fn pow2_capacity(size: usize) -> usize {
+    assert!(size > 0, "La taille du buffer doit être positive.");
+    size.checked_next_power_of_two()
+        .expect("Capacité trop grande pour être arrondie.")
+}
+
+// Case d'une position quelconque dans un stockage dont la capacité est une
+// puissance de deux ; elle ne change pas quand la position fait le tour de
+// `usize`
+#[inline]
+pub(crate) fn pow2_slot(position: usize, capacity: usize) -> usize {
+    debug_assert!(capacity.is_power_of_two());
+    position & (capacity - 1)
+}
+
+// Vue en lecture seule d'éléments initialisés comme des cases de stockage
+fn as_uninit_slice<T>(items: &[T]) -> &[MaybeUninit<T>] {
+    // SAFETY: `MaybeUninit<T>` a la même disposition mémoire que `T`.
+    unsafe { &*(items as *const [T] as *const [MaybeUninit<T>]) }
+}
+
+impl<T, S: Storage<T>> Drop for CircularBuffer<T, S> {
+    fn drop(&mut self) {
+        // Un buffer détruit ne signale plus de seuil
+        self.watermarks = None;
+        self.clear();
+    }
+}
+
+/// Seuls les éléments présents sont clonés, rangés à partir du début du
+/// stockage ; la capacité et la politique sont conservées, mais pas les
+/// fermetures d'éviction et de seuil, ni les statistiques.
+impl<T: Clone> Clone for CircularBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::with_policy(self.capacity(), self.policy);
+        clone.growth_factor = self.growth_factor;
+        for item in self.iter() {
+            clone.write_head(item.clone());
+        }
+        clone
+    }
+}
+
+/// Deux buffers sont égaux s'ils contiennent les mêmes éléments dans le même
+/// ordre logique, quelles que soient leur capacité et la position de "tail".
+impl<T, U, S, R> PartialEq<CircularBuffer<U, R>> for CircularBuffer<T, S>
+where
+    T: PartialEq<U>,
+    S: Storage<T>,
+    R: Storage<U>,
+{
+    fn eq(&self, other: &CircularBuffer<U, R>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T: Eq, S: Storage<T>> Eq for CircularBuffer<T, S> {}
+
+impl<T: PartialEq<U>, U, S: Storage<T>> PartialEq<[U]> for CircularBuffer<T, S> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl<T: PartialEq<U>, U, S: Storage<T>> PartialEq<&[U]> for CircularBuffer<T, S> {
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: PartialEq<U>, U, S: Storage<T>, const N: usize> PartialEq<[U; N]> for CircularBuffer<T, S> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, S: Storage<T>> PartialEq<Vec<U>> for CircularBuffer<T, S> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+/// Cohérent avec l'égalité logique : la longueur puis chaque élément, du plus
+/// ancien au plus récent.
+impl<T: Hash, S: Storage<T>> Hash for CircularBuffer<T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self.iter() {
+            item.hash(state);
+        }
+    }
+}
+
+/// Accès par indice logique : `buffer[0]` est le plus ancien élément et
+/// `buffer[buffer.len() - 1]` le plus récent. Panique hors limites.
+impl<T, S: Storage<T>> Index<usize> for CircularBuffer<T, S> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index)
+            .unwrap_or_else(|| panic!("{}", CircularBufferError::IndexOutOfBounds { index, len }))
+    }
+}
+
+impl<T, S: Storage<T>> IndexMut<usize> for CircularBuffer<T, S> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("{}", CircularBufferError::IndexOutOfBounds { index, len }))
+    }
+}
+
+impl<T: Copy, S: Storage<T>> CircularBuffer<T, S> {
+    /// Ajoute tous les éléments de `items`, comme autant d'appels à `push`,
+    /// en au plus deux copies de mémoire
+    pub fn extend_from_slice(&mut self, items: &[T]) {
+        match self.policy {
+            OverflowPolicy::Reject => {
+                self.push_slice(items);
+            }
+            OverflowPolicy::Overwrite => self.overwrite_slice(items),
+            OverflowPolicy::Grow => {
+                let mut new_size = self.capacity();
+                while new_size < self.len() + items.len() {
+                    new_size *= self.growth_factor;
+                }
+                if new_size != self.capacity() {
+                    self.relocate(new_size);
+                }
+                self.copy_to_head(items);
+            }
+        }
+    }
+
+    /// Copie autant d'éléments de `items` que la place libre le permet, sans
+    /// jamais écraser ; retourne le nombre d'éléments écrits
+    pub fn push_slice(&mut self, items: &[T]) -> usize {
+        let n = items.len().min(self.capacity() - self.len());
+        self.stats.rejected += (items.len() - n) as u64;
+        self.copy_to_head(&items[..n]);
+        n
+    }
+
+    /// Copie tous les éléments de `items` en écrasant les plus anciens si
+    /// nécessaire, quelle que soit la politique du buffer
+    pub fn overwrite_slice(&mut self, items: &[T]) {
+        let overflow = (self.len() + items.len()).saturating_sub(self.capacity());
+        // Évincer d'abord les éléments présents, puis les premiers éléments de
+        // la tranche qui seraient eux-mêmes écrasés
+        let existing = overflow.min(self.len());
+        self.missed += overflow as u64;
+        self.stats.overwrites += overflow as u64;
+        if self.on_evict.is_some() {
+            for _ in 0..existing {
+                if let Some(old) = self.pop_oldest() {
+                    self.evict(old);
+                }
+            }
+            for &item in &items[..overflow - existing] {
+                self.evict(item);
+            }
+        } else {
+            self.discard_front(existing);
+        }
+        self.copy_to_head(&items[overflow - existing..]);
+    }
+
+    /// Retire jusqu'à `dst.len()` éléments, les plus anciens d'abord, en les
+    /// copiant dans `dst` ; retourne le nombre d'éléments retirés
+    pub fn pop_into(&mut self, dst: &mut [T]) -> usize {
+        let n = dst.len().min(self.len());
+        let (front, back) = self.as_slices();
+        let first = n.min(front.len());
+        dst[..first].copy_from_slice(&front[..first]);
+        dst[first..n].copy_from_slice(&back[..n - first]);
+        self.release_front(n);
+        n
+    }
+
+    // Retire les `n` plus anciens éléments, déjà lus par un consommateur
+    fn release_front(&mut self, n: usize) {
+        let n = n.min(self.len());
+        self.discard_front(n);
+        self.record_pops(n);
+    }
+
+    // Retire les `n` plus anciens éléments sans les lire ; il n'y a rien à
+    // libérer pour un type `Copy`
+    fn discard_front(&mut self, n: usize) {
+        let n = n.min(self.len());
+        self.start = self.wrap(self.start + n);
+        self.tail = self.tail.wrapping_add(n as u64);
+    }
+
+    // Copie `items` à partir de "head" en au plus deux fois ; la place libre
+    // doit suffire
+    fn copy_to_head(&mut self, items: &[T]) {
+        debug_assert!(items.len() <= self.capacity() - self.len());
+        let head = self.head_index();
+        let first = items.len().min(self.capacity() - head);
+        let (start, wrapped) = as_uninit_slice(items).split_at(first);
+        self.buffer.slots_mut()[head..head + first].copy_from_slice(start);
+        self.buffer.slots_mut()[..wrapped.len()].copy_from_slice(wrapped);
+        self.head = self.head.wrapping_add(items.len() as u64);
+        self.record_pushes(items.len());
+    }
+}
+
+/// Affiche le contenu dans l'ordre logique, accompagné de la position de
+/// "tail" (plus ancien élément) et de "head" (prochaine case écrite).
+impl<T: fmt::Debug, S: Storage<T>> fmt::Debug for CircularBuffer<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircularBuffer")
+            .field("items", &DebugItems(self.iter()))
+            .field("tail", &self.tail)
+            .field("head", &self.head)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// Affiche le contenu dans l'ordre logique, par exemple `[20, 30, 40]`.
+impl<T: fmt::Display, S: Storage<T>> fmt::Display for CircularBuffer<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_items(self.iter(), f)
+    }
+}
+
+// Liste des éléments pour `Debug`
+pub(crate) struct DebugItems<I>(pub(crate) I);
+
+impl<I: Iterator + Clone> fmt::Debug for DebugItems<I>
+where
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+// Écrit `[a, b, c]` en transmettant les options de formatage à chaque élément
+pub(crate) fn display_items<T: fmt::Display>(
+    items: impl Iterator<Item = T>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    f.write_str("[")?;
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        item.fmt(f)?;
+    }
+    f.write_str("]")
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug, S: Storage<T>> CircularBuffer<T, S> {
+    /// Affiche tous les éléments du buffer
+    #[deprecated(note = "utiliser les implémentations de `Debug` ou `Display`")]
+    pub fn display(&self) {
+        print!("Buffer: ");
+        for (i, slot) in self.buffer.slots().iter().enumerate() {
+            if self.is_occupied(i) {
+                // SAFETY: la case est dans la zone occupée.
+                print!("{:?} ", unsafe { slot.assume_init_ref() });
+            } else {
+                print!("_ "); // Indique une case vide
+            }
+        }
+        println!();
+    }
+}
+
+impl<T: PartialEq, S: Storage<T>> CircularBuffer<T, S> {
+    /// Vérifie si un élément est présent dans le buffer
+    pub fn contains(&self, item: &T) -> bool {
+        self.iter().any(|val| val == item)
+    }
+}
+
+impl<T: Ord, S: Storage<T>> CircularBuffer<T, S> {
+    /// Recherche dichotomique de `item` dans un buffer trié, voir
+    /// [`binary_search_by`](Self::binary_search_by)
+    pub fn binary_search(&self, item: &T) -> Result<usize, usize> {
+        self.binary_search_by(|other| other.cmp(item))
+    }
+
+    /// Trie les éléments présents, en conservant l'ordre des éléments égaux
+    pub fn sort(&mut self) {
+        self.make_contiguous().sort();
+    }
+
+    /// Trie les éléments présents sans allocation, voir
+    /// [`sort_unstable_by`](Self::sort_unstable_by)
+    pub fn sort_unstable(&mut self) {
+        self.make_contiguous().sort_unstable();
+    }
+
+    /// Insère `item` à sa place dans un buffer trié, après les éléments égaux
+    ///
+    /// Si le buffer est plein, la [`OverflowPolicy`] s'applique comme pour
+    /// [`push`](Self::push) : avec `Overwrite`, le plus ancien, donc le plus
+    /// petit, est écrasé et rendu ; `item` lui-même est rendu s'il est plus
+    /// petit que tous les éléments.
+    pub fn insert_sorted(&mut self, item: T) -> Option<T> {
+        let mut index = self.partition_point(|other| other <= &item);
+        let mut evicted = None;
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Overwrite => {
+                    self.missed += 1;
+                    self.stats.overwrites += 1;
+                    if index == 0 {
+                        self.stats.pushes += 1;
+                        return self.evict(item);
+                    }
+                    evicted = self.pop_oldest().and_then(|old| self.evict(old));
+                    index -= 1;
+                }
+                OverflowPolicy::Reject => {
+                    self.stats.rejected += 1;
+                    return Some(item);
+                }
+                OverflowPolicy::Grow => self.relocate(self.capacity() * self.growth_factor),
+            }
+        }
+        self.write_head(item);
+        self.record_pushes(1);
+        // Faire reculer le nouvel élément jusqu'à sa place
+        for i in (index..self.len() - 1).rev() {
+            let (a, b) = (self.wrap(self.start + i), self.wrap(self.start + i + 1));
+            self.buffer.slots_mut().swap(a, b);
+        }
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop_fifo() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_push_ecrase_le_plus_ancien() {
+        let mut buffer = CircularBuffer::new(2);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.peek(), Some(&2));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_push_rend_l_element_ecrase() {
+        let mut buffer = CircularBuffer::new(2);
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert_eq!(buffer.push(3), Some(1));
+        assert_eq!(buffer.push(4), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_on_evict() {
+        use std::sync::{Arc, Mutex};
+
+        let evinces = Arc::new(Mutex::new(Vec::new()));
+        let journal = Arc::clone(&evinces);
+        let mut buffer = CircularBuffer::new(3).on_evict(move |x| journal.lock().unwrap().push(x));
+        for i in 1..=5 {
+            assert_eq!(buffer.push(i), None);
+        }
+        assert!(buffer.resize(1).is_ok());
+        assert_eq!(*evinces.lock().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(buffer.pop(), Some(5));
+    }
+
+    #[test]
+    fn test_iter_ordre_fifo() {
+        let mut buffer = CircularBuffer::new(5);
+        for i in 1..=6 {
+            buffer.push(i * 10);
+        }
+        let logique: Vec<_> = buffer.iter().copied().collect();
+        assert_eq!(logique, vec![20, 30, 40, 50, 60]);
+        let stockage: Vec<_> = buffer.iter_unordered().copied().collect();
+        assert_eq!(stockage, vec![60, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn test_iter_mut_apres_enroulement() {
+        let mut buffer = CircularBuffer::new(3);
+        for i in 1..=4 {
+            buffer.push(i as f32);
+        }
+        for echantillon in buffer.iter_mut() {
+            *echantillon *= 0.5;
+        }
+        assert_eq!(
+            buffer.iter().copied().collect::<Vec<_>>(),
+            vec![1.0, 1.5, 2.0]
+        );
+    }
+
+    #[test]
+    fn test_extend_from_slice() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.pop();
+        buffer.extend_from_slice(&[3, 4, 5]);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+        buffer.extend_from_slice(&[6, 7]);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+        buffer.extend_from_slice(&[8, 9, 10, 11, 12, 13]);
+        assert_eq!(
+            buffer.iter().copied().collect::<Vec<_>>(),
+            vec![10, 11, 12, 13]
+        );
+
+        let mut refuse = CircularBuffer::with_policy(3, OverflowPolicy::Reject);
+        refuse.extend_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(refuse.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let mut extensible = CircularBuffer::with_policy(2, OverflowPolicy::Grow);
+        extensible.extend_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(extensible.capacity(), 8);
+        assert_eq!(extensible.len(), 5);
+    }
+
+    #[test]
+    fn test_extend_from_slice_on_evict() {
+        use std::sync::{Arc, Mutex};
+
+        let evinces = Arc::new(Mutex::new(Vec::new()));
+        let journal = Arc::clone(&evinces);
+        let mut buffer = CircularBuffer::new(3).on_evict(move |x| journal.lock().unwrap().push(x));
+        buffer.push(1);
+        buffer.push(2);
+        buffer.extend_from_slice(&[3, 4, 5, 6, 7]);
+        assert_eq!(*evinces.lock().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7]);
+    }
+
+    #[test]
+    fn test_index_logique() {
+        let mut buffer = CircularBuffer::new(3);
+        for i in 1..=5 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer[0], 3);
+        assert_eq!(buffer[2], 5);
+        buffer[2] = 50;
+        assert_eq!(buffer.get(2), Some(&50));
+        assert_eq!(buffer.get(3), None);
+        if let Some(x) = buffer.get_mut(1) {
+            *x += 1;
+        }
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![3, 5, 50]);
+    }
+
+    #[test]
+    fn test_front_back() {
+        let mut buffer = CircularBuffer::new(2);
+        assert_eq!(buffer.front(), None);
+        assert_eq!(buffer.back(), None);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        assert_eq!(buffer.front(), Some(&2));
+        assert_eq!(buffer.back(), Some(&3));
+        if let Some(dernier) = buffer.back_mut() {
+            *dernier = 30;
+        }
+        if let Some(premier) = buffer.front_mut() {
+            *premier = 20;
+        }
+        assert_eq!(buffer.pop(), Some(20));
+        assert_eq!(buffer.front(), buffer.back());
+        assert_eq!(buffer.pop(), Some(30));
+    }
+
+    #[test]
+    fn test_deque_deux_extremites() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.push(2);
+        buffer.push_front(1);
+        buffer.push(3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(buffer.push_front(0), Some(3));
+        assert_eq!(buffer.pop_back(), Some(2));
+        assert_eq!(buffer.pop_back(), Some(1));
+        assert_eq!(buffer.pop(), Some(0));
+        assert_eq!(buffer.pop_back(), None);
+
+        let mut refuse = CircularBuffer::with_policy(1, OverflowPolicy::Reject);
+        refuse.push_front(1);
+        assert_eq!(refuse.push_front(2), Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "hors limites")]
+    fn test_index_hors_limites() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.push(1);
+        let _ = buffer[1];
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut buffer = CircularBuffer::new(5);
+        for i in 1..=8 {
+            buffer.push(i);
+        }
+        buffer.retain(|x| x % 2 == 0);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![4, 6, 8]);
+        buffer.push(9);
+        buffer.retain(|_| true);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![4, 6, 8, 9]);
+        buffer.retain(|_| false);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_as_slices() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(buffer.as_slices(), (&[1, 2, 3][..], &[][..]));
+        buffer.extend_from_slice(&[4, 5, 6]);
+        assert_eq!(buffer.as_slices(), (&[3, 4][..], &[5, 6][..]));
+        let (debut, fin) = buffer.as_mut_slices();
+        debut[0] = 30;
+        fin[1] = 60;
+        assert_eq!(
+            buffer.iter().copied().collect::<Vec<_>>(),
+            vec![30, 4, 5, 60]
+        );
+        assert_eq!(
+            CircularBuffer::<i32>::new(2).as_slices(),
+            (&[][..], &[][..])
+        );
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(buffer.make_contiguous(), &mut [3, 4, 5, 6]);
+        assert_eq!(buffer.as_slices(), (&[3, 4, 5, 6][..], &[][..]));
+        buffer.pop();
+        buffer.push(7);
+        buffer.make_contiguous().reverse();
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![7, 6, 5, 4]);
+        buffer.push(8);
+        assert_eq!(buffer.front(), Some(&6));
+    }
+
+    #[test]
+    fn test_push_slice_et_overwrite_slice() {
+        let mut buffer = CircularBuffer::new(4);
+        assert_eq!(buffer.push_slice(&[1, 2, 3]), 3);
+        assert_eq!(buffer.push_slice(&[4, 5, 6]), 1);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        buffer.pop();
+        buffer.overwrite_slice(&[5, 6, 7]);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6, 7]);
+
+        let mut refuse = CircularBuffer::with_policy(2, OverflowPolicy::Reject);
+        refuse.overwrite_slice(&[1, 2, 3]);
+        assert_eq!(refuse.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_pop_into() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+        let mut dst = [0; 3];
+        assert_eq!(buffer.pop_into(&mut dst), 3);
+        assert_eq!(dst, [3, 4, 5]);
+        assert_eq!(buffer.pop_into(&mut dst), 1);
+        assert_eq!(dst[0], 6);
+        assert_eq!(buffer.pop_into(&mut dst), 0);
+        buffer.push(7);
+        assert_eq!(buffer.pop(), Some(7));
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut buffer = CircularBuffer::with_policy(3, OverflowPolicy::Grow);
+        buffer.extend_from_slice(&[1, 2, 3]);
+        buffer.pop();
+        buffer.push(4);
+        let copie = buffer.clone();
+        assert_eq!(copie.capacity(), 3);
+        assert_eq!(copie.policy(), OverflowPolicy::Grow);
+        assert_eq!(copie.as_slices(), (&[2, 3, 4][..], &[][..]));
+        buffer.push(5);
+        assert_eq!(copie.len(), 3);
+    }
+
+    #[test]
+    fn test_egalite_logique() {
+        let mut a = CircularBuffer::new(3);
+        a.extend([0, 1, 2, 3]);
+        let mut b = CircularBuffer::new(5);
+        b.extend_from_slice(&[1, 2, 3]);
+        assert_ne!(a.as_slices(), b.as_slices());
+        assert!(a == b);
+        assert!(a == [1, 2, 3]);
+        let tranche: &[i32] = &[1, 2, 3];
+        assert!(a == tranche);
+        assert!(a == vec![1, 2, 3]);
+        b.push(4);
+        assert!(a != b);
+        assert!(a != [1, 2]);
+    }
+
+    #[test]
+    fn test_hash_logique() {
+        use std::collections::HashMap;
+
+        let mut a = CircularBuffer::new(3);
+        a.extend([0, 1, 2, 3]);
+        let mut b = CircularBuffer::new(4);
+        b.extend([1, 2, 3]);
+
+        let mut memo = HashMap::new();
+        memo.insert(a, "resultat");
+        assert_eq!(memo.get(&b), Some(&"resultat"));
+        b.push(4);
+        assert_eq!(memo.get(&b), None);
+    }
+
+    #[test]
+    fn test_debug_et_display() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend([1, 2, 3, 4]);
+        assert_eq!(format!("{}", buffer), "[2, 3, 4]");
+        assert_eq!(format!("{:02}", buffer), "[02, 03, 04]");
+        assert_eq!(
+            format!("{:?}", buffer),
+            "CircularBuffer { items: [2, 3, 4], tail: 1, head: 4, capacity: 3 }"
+        );
+        assert_eq!(buffer, [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_try_push_refuse_si_plein() {
+        let mut buffer = CircularBuffer::new(2);
+        assert_eq!(buffer.try_push(1), Ok(()));
+        assert_eq!(buffer.try_push(2), Ok(()));
+        assert_eq!(buffer.try_push(3), Err(3));
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(buffer.try_push(3), Ok(()));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_politique_reject() {
+        let mut buffer = CircularBuffer::with_policy(2, OverflowPolicy::Reject);
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert_eq!(buffer.push(3), Some(3));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.peek(), Some(&1));
+    }
+
+    #[test]
+    fn test_politique_grow() {
+        let mut buffer = CircularBuffer::with_policy(2, OverflowPolicy::Grow);
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(buffer.pop(), Some(1));
+        buffer.push(3);
+        buffer.push(4);
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(buffer.policy(), OverflowPolicy::Grow);
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), Some(4));
+    }
+
+    #[test]
+    fn test_resize_conserve_l_ordre() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        buffer.push(4);
+        assert!(buffer.resize(5).is_ok());
+        assert_eq!(buffer.capacity(), 5);
+        buffer.push(5);
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), Some(4));
+        assert_eq!(buffer.pop(), Some(5));
+    }
+
+    #[test]
+    fn test_resize_reduit_garde_les_plus_recents() {
+        // Contenu enroulé : stockage [5, 6, 3, 4], plus ancien = 3
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend(1..=6);
+        assert!(buffer.resize(2).is_ok());
+        assert_eq!(buffer, [5, 6]);
+        assert_eq!(buffer.capacity(), 2);
+        buffer.push(7);
+        assert_eq!(buffer, [6, 7]);
+
+        // Contenu non enroulé, buffer partiellement rempli
+        let mut buffer = CircularBuffer::new(5);
+        buffer.extend(1..=4);
+        buffer.pop();
+        assert!(buffer.resize(1).is_ok());
+        assert_eq!(buffer, [4]);
+    }
+
+    #[test]
+    fn test_resize_keep_oldest() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend(1..=6);
+        assert!(buffer.resize_keep_oldest(3).is_ok());
+        assert_eq!(buffer, [3, 4, 5]);
+        buffer.push(7);
+        assert_eq!(buffer, [4, 5, 7]);
+        assert_eq!(
+            buffer.resize_keep_oldest(0),
+            Err(CircularBufferError::ZeroCapacity)
+        );
+        assert!(buffer.resize_keep_oldest(6).is_ok());
+        assert_eq!(buffer, [4, 5, 7]);
+    }
+
+    #[test]
+    fn test_resize_zero_refuse() {
+        let mut buffer = CircularBuffer::<i32>::new(3);
+        assert_eq!(buffer.resize(0), Err(CircularBufferError::ZeroCapacity));
+        assert_eq!(buffer.capacity(), 3);
+    }
+
+    #[test]
+    fn test_type_sans_clone() {
+        // Un type qui n'implémente ni Debug, ni Clone, ni PartialEq
+        struct Jeton(u32);
+
+        let mut buffer = CircularBuffer::new(2);
+        buffer.push(Jeton(1));
+        buffer.push(Jeton(2));
+        buffer.push(Jeton(3));
+        buffer.clear();
+        buffer.push(Jeton(4));
+        assert!(buffer.resize(4).is_ok());
+        assert_eq!(buffer.pop().map(|j| j.0), Some(4));
+    }
+
+    #[test]
+    fn test_elements_liberes() {
+        let jeton = Rc::new(());
+        {
+            let mut buffer = CircularBuffer::new(3);
+            for _ in 0..5 {
+                buffer.push(Rc::clone(&jeton));
+            }
+            assert_eq!(Rc::strong_count(&jeton), 4);
+            assert!(buffer.resize(2).is_ok());
+            assert_eq!(Rc::strong_count(&jeton), 3);
+            buffer.shrink_to_fit();
+            drop(buffer.pop());
+            assert_eq!(Rc::strong_count(&jeton), 2);
+        }
+        assert_eq!(Rc::strong_count(&jeton), 1);
+    }
+
+    #[test]
+    fn test_clear_sans_reallocation() {
+        let jeton = Rc::new(());
+        let mut buffer = CircularBuffer::new(3);
+        for _ in 0..4 {
+            buffer.push(Rc::clone(&jeton));
+        }
+        let stockage = buffer.buffer.as_ptr();
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), 3);
+        assert_eq!(buffer.buffer.as_ptr(), stockage);
+        assert_eq!(Rc::strong_count(&jeton), 1);
+        buffer.push(Rc::clone(&jeton));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_clear_with() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend(1..=5);
+        let mut recus = Vec::new();
+        buffer.clear_with(|x| recus.push(x));
+        assert_eq!(recus, vec![3, 4, 5]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_shrink_to_fit_puis_push() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.shrink_to_fit();
+        assert_eq!(buffer.capacity(), 2);
+        buffer.push(3);
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        buffer.shrink_to_fit();
+        assert_eq!(buffer.capacity(), 1);
+    }
+
+    #[test]
+    fn test_capacite_puissance_de_deux() {
+        let mut buffer = CircularBuffer::with_pow2_capacity(5);
+        assert_eq!(buffer.capacity(), 8);
+        buffer.extend(1..=20);
+        buffer.push_front(0);
+        // Buffer plein : `push_front` écrase le plus récent
+        assert_eq!(buffer, [0, 13, 14, 15, 16, 17, 18, 19]);
+        assert_eq!(buffer.pop_back(), Some(19));
+        assert_eq!(buffer[1], 13);
+        assert_eq!(CircularBuffer::<u8>::with_pow2_capacity(1).capacity(), 1);
+    }
+
+    #[test]
+    fn test_positions_absolues() {
+        let mut buffer = CircularBuffer::with_policy(3, OverflowPolicy::Grow);
+        buffer.extend(0..5);
+        assert_eq!((buffer.tail, buffer.head), (0, 5));
+        assert_eq!(buffer.pop(), Some(0));
+        buffer.resize(4).unwrap();
+        assert_eq!((buffer.tail, buffer.head), (1, 5));
+        buffer.clear();
+        buffer.push(9);
+        assert_eq!((buffer.tail, buffer.head), (5, 6));
+        assert_eq!(buffer, [9]);
+    }
+
+    #[test]
+    fn test_numeros_de_sequence() {
+        let mut buffer = CircularBuffer::new(2);
+        assert_eq!(buffer.push_seq('a'), Ok(0));
+        assert_eq!(buffer.push_seq('b'), Ok(1));
+        assert_eq!(buffer.push_seq('c'), Ok(2));
+        assert_eq!(buffer.pop_seq(), Some((1, 'b')));
+        assert_eq!(buffer.pop_seq(), Some((2, 'c')));
+        assert_eq!(buffer.pop_seq(), None);
+
+        let mut buffer = CircularBuffer::with_policy(1, OverflowPolicy::Reject);
+        assert_eq!(buffer.push_seq(1), Ok(0));
+        assert_eq!(buffer.push_seq(2), Err(2));
+    }
+
+    #[test]
+    fn test_elements_manques() {
+        let mut buffer = CircularBuffer::new(2);
+        buffer.extend(1..=5);
+        assert_eq!(buffer.missed(), 3);
+        assert_eq!(buffer.pop(), Some(4));
+        assert_eq!(buffer.missed(), 0);
+        buffer.extend_from_slice(&[6, 7, 8]);
+        assert_eq!(buffer.missed(), 2);
+        assert_eq!(buffer, [7, 8]);
+    }
+
+    #[test]
+    fn test_elements_manques_par_chaque_lecture() {
+        let mut buffer = CircularBuffer::new(2);
+        buffer.extend(1..=4);
+        assert_eq!(buffer.missed(), 2);
+        assert_eq!(buffer.drain().collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(buffer.missed(), 0);
+
+        buffer.extend(5..=7);
+        assert_eq!(buffer.drain().next_back(), Some(7));
+        assert_eq!(buffer.missed(), 0);
+
+        buffer.extend([8, 9]);
+        assert_eq!(buffer.push_front(10), Some(9));
+        assert_eq!(buffer.missed(), 1);
+        let mut lus = [0; 1];
+        assert_eq!(buffer.pop_into(&mut lus), 1);
+        assert_eq!((lus, buffer.missed()), ([10], 0));
+    }
+
+    #[test]
+    fn test_statistiques() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend(1..=5);
+        buffer.pop();
+        buffer.pop_back();
+        let mut refus = CircularBuffer::with_policy(1, OverflowPolicy::Reject);
+        refus.push(1);
+        assert_eq!(refus.try_push(2), Err(2));
+        assert_eq!(refus.push_slice(&[3, 4]), 0);
+
+        let stats = buffer.stats();
+        assert_eq!((stats.len, stats.capacity), (1, 3));
+        assert_eq!((stats.pushes, stats.pops, stats.overwrites), (5, 2, 2));
+        assert_eq!(stats.high_water_mark, 3);
+        assert_eq!(refus.stats().rejected, 3);
+        buffer.reset_stats();
+        assert_eq!(buffer.stats().pushes, 0);
+        assert_eq!(buffer.stats().high_water_mark, 1);
+    }
+
+    #[test]
+    fn test_buffer_trie() {
+        let mut buffer = CircularBuffer::new(4);
+        for x in [5, 1, 4, 2, 3] {
+            buffer.insert_sorted(x);
+        }
+        assert_eq!(buffer, [2, 3, 4, 5]);
+        assert_eq!(buffer.insert_sorted(1), Some(1));
+        assert_eq!(buffer.insert_sorted(4), Some(2));
+        assert_eq!(buffer, [3, 4, 4, 5]);
+        assert_eq!(buffer.binary_search(&5), Ok(3));
+        assert_eq!(buffer.binary_search(&1), Err(0));
+        assert_eq!(buffer.partition_point(|&x| x < 4), 1);
+        assert_eq!(buffer.binary_search_by_key(&10, |&x| x * 2), Ok(3));
+    }
+
+    #[test]
+    fn test_tri() {
+        let mut buffer = CircularBuffer::new(5);
+        buffer.extend([9, 3, 7, 1, 8, 2, 6]);
+        buffer.sort();
+        assert_eq!(buffer, [1, 2, 6, 7, 8]);
+        assert_eq!(buffer.binary_search(&7), Ok(3));
+        buffer.push(0);
+        buffer.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(buffer, [8, 7, 6, 2, 0]);
+        let mut paires = CircularBuffer::new(3);
+        paires.extend([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+        paires.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(paires, [(1, 'b'), (1, 'd'), (2, 'c')]);
+    }
+
+    #[test]
+    fn test_position_et_find() {
+        let mut buffer = CircularBuffer::new(4);
+        buffer.extend([1, 2, 3, 4, 5, 6]);
+        assert_eq!(buffer.position(|&x| x % 2 == 0), Some(1));
+        assert_eq!(buffer.position(|&x| x == 1), None);
+        assert_eq!(buffer.find(|&x| x > 4), Some(&5));
+        assert!(buffer.contains(&6));
+        assert!(!buffer.contains(&2));
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            CircularBuffer::<i32>::try_new(0).err(),
+            Some(CircularBufferError::ZeroCapacity)
+        );
+        let buffer = CircularBuffer::<i32>::try_new(2).unwrap();
+        assert_eq!(buffer.capacity(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_zero_panique() {
+        let _ = CircularBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    fn test_push_or_merge() {
+        let mut journal = CircularBuffer::new(3);
+        let fusion = |dernier: &mut (&'static str, usize), ligne: (&'static str, usize)| {
+            if dernier.0 == ligne.0 {
+                dernier.1 += ligne.1;
+                Ok(())
+            } else {
+                Err(ligne)
+            }
+        };
+        for ligne in ["connexion", "délai", "délai", "délai", "connexion"] {
+            assert_eq!(journal.push_or_merge((ligne, 1), fusion), None);
+        }
+        assert_eq!(
+            journal.iter().copied().collect::<Vec<_>>(),
+            [("connexion", 1), ("délai", 3), ("connexion", 1)]
+        );
+    }
+
+    #[test]
+    fn test_drain_while() {
+        let mut horodatages = CircularBuffer::new(8);
+        horodatages.extend([1, 3, 5, 8, 2, 9]);
+        let anciens: Vec<_> = horodatages.drain_while(|&t| t < 6).collect();
+        assert_eq!(anciens, [1, 3, 5]);
+        assert_eq!(horodatages.pop_while(|&t| t >= 8), 1);
+        assert_eq!(horodatages.peek(), Some(&2));
+
+        // Abandonné, l'itérateur retire quand même le reste du préfixe
+        horodatages.extend([4, 6]);
+        assert_eq!(horodatages.drain_while(|&t| t != 6).next(), Some(2));
+        assert_eq!(horodatages.iter().collect::<Vec<_>>(), [&6]);
+    }
+
+    #[test]
+    fn test_pop_par_lots() {
+        let mut buffer = CircularBuffer::new(5);
+        buffer.extend(1..=5);
+        assert_eq!(buffer.pop_up_to(2), [1, 2]);
+        assert_eq!(buffer.pop_exact(4), None);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.pop_exact(3), Some(vec![3, 4, 5]));
+        assert_eq!(buffer.pop_up_to(4), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_push_all() {
+        let mut trames = CircularBuffer::with_policy(4, OverflowPolicy::Reject);
+        assert_eq!(trames.push_all([1, 2, 3]), Ok(()));
+        assert_eq!(
+            trames.push_all([4, 5]),
+            Err(CircularBufferError::NotEnoughSpace {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(trames.len(), 3);
+
+        let mut buffer = CircularBuffer::new(3);
+        buffer.push(0);
+        assert_eq!(buffer.push_all(1..=3), Ok(()));
+        assert_eq!(buffer, [1, 2, 3][..]);
+        assert!(buffer.push_all(0..4).is_err());
+        assert_eq!(buffer, [1, 2, 3][..]);
+    }
+
+    #[test]
+    fn test_split_off_et_append() {
+        let mut avant = CircularBuffer::new(5);
+        avant.extend(0..7);
+        let mut apres = avant.split_off(3);
+        assert_eq!(avant, [2, 3, 4][..]);
+        assert_eq!(apres, [5, 6][..]);
+        assert_eq!(apres.capacity(), 5);
+
+        apres.append(&mut avant);
+        assert!(avant.is_empty());
+        assert_eq!(apres, [5, 6, 2, 3, 4][..]);
+
+        let mut plein = CircularBuffer::with_policy(2, OverflowPolicy::Reject);
+        plein.push(1);
+        let mut reste = CircularBuffer::from_iter([2, 3]);
+        plein.append(&mut reste);
+        assert_eq!(plein, [1, 2][..]);
+        assert_eq!(reste, [3][..]);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let jeton = Rc::new(());
+        let mut buffer = CircularBuffer::new(4);
+        for _ in 0..6 {
+            buffer.push(Rc::clone(&jeton));
+        }
+        buffer.truncate_front(3);
+        assert_eq!((buffer.len(), Rc::strong_count(&jeton)), (3, 4));
+        buffer.truncate_back(1);
+        assert_eq!((buffer.len(), Rc::strong_count(&jeton)), (1, 2));
+        buffer.truncate_back(5);
+        assert_eq!(buffer.len(), 1);
+
+        let mut nombres = CircularBuffer::new(4);
+        nombres.extend(1..=6);
+        nombres.truncate_front(2);
+        assert_eq!(nombres, [5, 6][..]);
+        nombres.extend([7, 8]);
+        nombres.truncate_back(3);
+        assert_eq!(nombres, [5, 6, 7][..]);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let mut plein = CircularBuffer::new(4);
+        plein.extend(1..=4);
+        plein.rotate_left(1);
+        assert_eq!(plein, [2, 3, 4, 1][..]);
+        plein.rotate_right(3);
+        assert_eq!(plein, [3, 4, 1, 2][..]);
+
+        let mut partiel = CircularBuffer::new(6);
+        partiel.extend(1..=5);
+        partiel.pop();
+        partiel.extend([6, 7]);
+        assert_eq!(partiel, [2, 3, 4, 5, 6, 7][..]);
+        partiel.pop();
+        partiel.rotate_left(1);
+        assert_eq!(partiel, [4, 5, 6, 7, 3][..]);
+        partiel.rotate_left(4);
+        assert_eq!(partiel, [3, 4, 5, 6, 7][..]);
+        partiel.rotate_right(2);
+        assert_eq!(partiel, [6, 7, 3, 4, 5][..]);
+        partiel.rotate_left(0);
+        assert_eq!(partiel.len(), 5);
+    }
+
+    #[test]
+    fn test_remove_et_insert() {
+        let mut commandes = CircularBuffer::new(6);
+        commandes.extend(0..8);
+        assert_eq!(commandes.remove(1), Ok(3));
+        assert_eq!(commandes.remove(3), Ok(6));
+        assert_eq!(
+            commandes.remove(4),
+            Err(CircularBufferError::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        assert_eq!(commandes, [2, 4, 5, 7][..]);
+
+        assert_eq!(commandes.insert(1, 3), Ok(()));
+        assert_eq!(commandes.insert(4, 6), Ok(()));
+        assert_eq!(commandes, [2, 3, 4, 5, 6, 7][..]);
+        assert_eq!(commandes.insert(0, 1), Err(1));
+        commandes.pop();
+        assert_eq!(commandes.insert(6, 9), Err(9));
+        assert_eq!(commandes.insert(5, 8), Ok(()));
+        assert_eq!(commandes, [3, 4, 5, 6, 7, 8][..]);
+    }
+
+    #[test]
+    fn test_swap_et_swap_remove() {
+        let mut requetes = CircularBuffer::new(5);
+        requetes.extend(['a', 'b', 'c', 'd', 'e', 'f']);
+        requetes.swap(0, 3);
+        assert_eq!(requetes, ['e', 'c', 'd', 'b', 'f'][..]);
+        assert_eq!(requetes.swap_remove_back(1), Some('c'));
+        assert_eq!(requetes, ['e', 'f', 'd', 'b'][..]);
+        assert_eq!(requetes.swap_remove_front(2), Some('d'));
+        assert_eq!(requetes, ['f', 'e', 'b'][..]);
+        assert_eq!(requetes.swap_remove_front(3), None);
+        assert_eq!(requetes.swap_remove_back(3), None);
+    }
+
+    #[test]
+    fn test_windows_et_chunks() {
+        // Contenu enroulé : [3, 4] en fin de stockage, [5, 6, 7] au début
+        let mut signal = CircularBuffer::new(5);
+        signal.extend(0..8);
+        let sommes: Vec<i32> = signal.windows(3).map(|f| f.sum()).collect();
+        assert_eq!(sommes, [12, 15, 18]);
+        assert_eq!(signal.windows(5).len(), 1);
+        assert_eq!(signal.windows(6).next().map(|f| f.len()), None);
+
+        let morceaux: Vec<Vec<i32>> = signal.chunks(2).map(|m| m.copied().collect()).collect();
+        assert_eq!(morceaux, [vec![3, 4], vec![5, 6], vec![7]]);
+        assert_eq!(signal.chunks(2).len(), 3);
+        assert_eq!(
+            CircularBuffer::<i32>::new(2)
+                .chunks(4)
+                .next()
+                .map(|m| m.len()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_first_n_et_last_n() {
+        let mut journal = CircularBuffer::new(5);
+        journal.extend(0..8);
+        assert_eq!(journal.last_n(2).collect::<Vec<_>>(), [&6, &7]);
+        assert_eq!(
+            journal.last_n(4).rev().collect::<Vec<_>>(),
+            [&7, &6, &5, &4]
+        );
+        assert_eq!(journal.first_n(3).collect::<Vec<_>>(), [&3, &4, &5]);
+        assert_eq!(journal.first_n(9).len(), 5);
+        assert_eq!(journal.last_n(0).len(), 0);
+    }
+
+    #[test]
+    fn test_push_front_sur_buffer_vide() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.push_front(1);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&1]);
+        assert_eq!(buffer.pop(), Some(1));
+        assert!(buffer.is_empty());
+
+        let mut buffer = CircularBuffer::new(3);
+        let mut curseur = buffer.cursor();
+        buffer.push_front(2);
+        buffer.push_front(1);
+        buffer.extend_from_slice(&[3]);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&1, &2, &3]);
+        // Les éléments ajoutés devant précèdent le curseur
+        assert_eq!(curseur.next(&buffer), Ok(Some(&3)));
+        assert_eq!(buffer.pop_into(&mut [0; 2]), 2);
+        assert_eq!(buffer.pop_seq(), Some((0, 3)));
+    }
+}
@@ -0,0 +1,134 @@
+//! Journal en mémoire pour la crate `log` (fonctionnalité `log`).
+//!
+//! Un [`RingLogger`] garde les derniers messages formatés, comme une boîte
+//! noire : ils sont écrits sur demande ou lorsqu'un thread panique.
+
+use std::io::{self, Write};
+use std::panic;
+use std::sync::{Mutex, MutexGuard};
+
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::CircularBuffer;
+
+/// Implémentation de [`log::Log`] qui conserve les `capacity` derniers
+/// messages dans un buffer circulaire.
+pub struct RingLogger {
+    records: Mutex<CircularBuffer<String>>,
+    level: LevelFilter,
+}
+
+impl RingLogger {
+    /// Création d'un journal gardant `capacity` messages de niveau `level` ou
+    /// plus grave
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize, level: LevelFilter) -> Self {
+        Self {
+            records: Mutex::new(CircularBuffer::new(capacity)),
+            level,
+        }
+    }
+
+    /// Installe le journal comme journal global de la crate `log`
+    ///
+    /// Le journal vit jusqu'à la fin du programme ; la référence rendue
+    /// permet de relire les messages.
+    pub fn install(self) -> Result<&'static Self, SetLoggerError> {
+        let logger: &'static Self = Box::leak(Box::new(self));
+        log::set_logger(logger)?;
+        log::set_max_level(logger.level);
+        Ok(logger)
+    }
+
+    /// Écrit les messages conservés sur la sortie d'erreur lorsqu'un thread
+    /// panique, avant le gestionnaire de panique déjà en place
+    pub fn dump_on_panic(&'static self) {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = self.dump(&mut io::stderr().lock());
+            previous(info);
+        }));
+    }
+
+    /// Retourne une copie des messages conservés, du plus ancien au plus récent
+    pub fn records(&self) -> Vec<String> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Écrit les messages conservés dans `out`, un par ligne
+    pub fn dump(&self, out: &mut impl Write) -> io::Result<()> {
+        for record in self.lock().iter() {
+            writeln!(out, "{}", record)?;
+        }
+        out.flush()
+    }
+
+    /// Oublie tous les messages conservés
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    // Un `log` interrompu par une panique laisse le buffer cohérent
+    fn lock(&self) -> MutexGuard<'_, CircularBuffer<String>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            let line = format!("{} {}: {}", record.level(), record.target(), record.args());
+            self.lock().push(line);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    fn journaliser(logger: &RingLogger, level: Level, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target("app")
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_derniers_messages() {
+        let logger = RingLogger::new(2, LevelFilter::Info);
+        journaliser(&logger, Level::Info, "démarrage");
+        journaliser(&logger, Level::Debug, "ignoré");
+        journaliser(&logger, Level::Warn, "disque presque plein");
+        journaliser(&logger, Level::Error, "écriture impossible");
+        assert_eq!(
+            logger.records(),
+            [
+                "WARN app: disque presque plein",
+                "ERROR app: écriture impossible"
+            ]
+        );
+
+        let mut sortie = Vec::new();
+        logger.dump(&mut sortie).unwrap();
+        assert_eq!(
+            String::from_utf8(sortie).unwrap(),
+            "WARN app: disque presque plein\nERROR app: écriture impossible\n"
+        );
+        logger.clear();
+        assert!(logger.records().is_empty());
+    }
+}
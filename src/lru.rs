@@ -0,0 +1,330 @@
+//! Cache LRU et ensemble des derniers éléments distincts, sur un buffer
+//! circulaire (fonctionnalité `std`).
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+/// Cache de capacité fixe qui écarte l'entrée la moins récemment utilisée.
+///
+/// Les entrées sont rangées dans un buffer circulaire, de la moins à la plus
+/// récemment utilisée, et une table associe chaque clé à sa position. Une
+/// entrée utilisée est déplacée en queue en laissant une case vide derrière
+/// elle ; le buffer, deux fois plus grand que le cache, est compacté lorsqu'il
+/// est plein, ce qui garde `get` et `insert` en O(1) amorti.
+pub struct LruRing<K, V> {
+    // Une case vide est une entrée déplacée ou retirée
+    ring: CircularBuffer<Option<(K, V)>>,
+    // Position absolue de chaque clé dans `ring`
+    index: HashMap<K, u64>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruRing<K, V> {
+    /// Création d'un cache vide de `capacity` entrées
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ring: CircularBuffer::with_policy(2 * capacity, OverflowPolicy::Reject),
+            index: HashMap::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Retourne la valeur associée à `key` et la marque comme la plus
+    /// récemment utilisée
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.refresh(key).map(|(_, value)| &*value)
+    }
+
+    /// Comme [`get`](Self::get), mais la valeur retournée est modifiable
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.refresh(key).map(|(_, value)| value)
+    }
+
+    /// Retourne la valeur associée à `key` sans changer l'ordre d'utilisation
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &position = self.index.get(key)?;
+        let (_, value) = self.ring.get(self.offset(position))?.as_ref()?;
+        Some(value)
+    }
+
+    /// Vérifie si `key` est dans le cache, sans changer l'ordre d'utilisation
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Ajout ou remplacement d'une entrée, qui devient la plus récemment
+    /// utilisée
+    ///
+    /// L'entrée écartée est rendue : l'ancienne valeur de la même clé, ou
+    /// l'entrée la moins récemment utilisée si le cache était plein.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some((_, current)) = self.refresh(&key) {
+            let old = core::mem::replace(current, value);
+            return Some((key, old));
+        }
+        let evicted = if self.index.len() == self.capacity {
+            self.pop_lru()
+        } else {
+            None
+        };
+        if self.ring.is_full() {
+            self.compact();
+        }
+        if let Ok(position) = self.ring.push_seq(Some((key.clone(), value))) {
+            self.index.insert(key, position);
+        }
+        evicted
+    }
+
+    /// Retrait de l'entrée associée à `key`
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let position = self.index.remove(key)?;
+        let offset = self.offset(position);
+        let (_, value) = self.ring.get_mut(offset)?.take()?;
+        Some(value)
+    }
+
+    /// Retrait de l'entrée la moins récemment utilisée
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        while let Some(slot) = self.ring.pop() {
+            if let Some((key, value)) = slot {
+                self.index.remove(&key);
+                return Some((key, value));
+            }
+        }
+        None
+    }
+
+    /// Parcourt les entrées, de la moins à la plus récemment utilisée
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.ring.iter().flatten().map(|(key, value)| (key, value))
+    }
+
+    /// Retourne le nombre d'entrées
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Vérifie si le cache est vide
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Retourne le nombre maximal d'entrées
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Vide le cache
+    pub fn clear(&mut self) {
+        self.ring.clear();
+        self.index.clear();
+    }
+
+    // Indice logique dans `ring` d'une position absolue
+    fn offset(&self, position: u64) -> usize {
+        position.wrapping_sub(self.ring.tail) as usize
+    }
+
+    // Déplace l'entrée de `key` en queue et la retourne
+    fn refresh<Q>(&mut self, key: &Q) -> Option<&mut (K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &position = self.index.get(key)?;
+        if position != self.ring.head.wrapping_sub(1) {
+            if self.ring.is_full() {
+                self.compact();
+            }
+            let offset = self.offset(self.index[key]);
+            let entry = self.ring.get_mut(offset)?.take();
+            let position = self.ring.push_seq(entry).ok()?;
+            *self.index.get_mut(key)? = position;
+        }
+        self.ring.back_mut()?.as_mut()
+    }
+
+    // Retire les cases vides et met à jour les positions ; au moins la moitié
+    // du buffer est libre ensuite
+    fn compact(&mut self) {
+        self.ring.retain(Option::is_some);
+        let tail = self.ring.tail;
+        for (i, slot) in self.ring.iter().enumerate() {
+            if let Some((key, _)) = slot {
+                if let Some(position) = self.index.get_mut(key) {
+                    *position = tail.wrapping_add(i as u64);
+                }
+            }
+        }
+    }
+}
+
+/// Ensemble des `capacity` derniers éléments distincts reçus, par exemple une
+/// fenêtre de déduplication d'identifiants.
+///
+/// Un élément déjà présent est soit ignoré ([`insert`](Self::insert)), et il
+/// garde sa place, soit ramené en queue
+/// ([`insert_or_refresh`](Self::insert_or_refresh)). Une fois plein, l'ajout
+/// d'un nouvel élément écarte le plus ancien.
+pub struct RingSet<T> {
+    items: LruRing<T, ()>,
+}
+
+impl<T: Hash + Eq + Clone> RingSet<T> {
+    /// Création d'un ensemble vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: LruRing::new(capacity),
+        }
+    }
+
+    /// Ajout d'un élément s'il n'est pas déjà présent ; retourne `true` s'il
+    /// a été ajouté
+    pub fn insert(&mut self, item: T) -> bool {
+        if self.items.contains_key(&item) {
+            return false;
+        }
+        self.items.insert(item, ());
+        true
+    }
+
+    /// Ajout d'un élément, ou déplacement en queue s'il est déjà présent ;
+    /// retourne `true` s'il a été ajouté
+    pub fn insert_or_refresh(&mut self, item: T) -> bool {
+        let added = !self.items.contains_key(&item);
+        self.items.insert(item, ());
+        added
+    }
+
+    /// Vérifie si un élément égal est présent
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.items.contains_key(item)
+    }
+
+    /// Retrait d'un élément ; retourne `true` s'il était présent
+    pub fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.items.remove(item).is_some()
+    }
+
+    /// Retrait de l'élément le plus ancien
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        self.items.pop_lru().map(|(item, ())| item)
+    }
+
+    /// Parcourt les éléments, du plus ancien au plus récent
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().map(|(item, ())| item)
+    }
+
+    /// Retourne le nombre d'éléments
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Vérifie si l'ensemble est vide
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Retourne le nombre maximal d'éléments
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Vide l'ensemble
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecarte_le_moins_utilise() {
+        let mut cache = LruRing::new(2);
+        assert_eq!(cache.insert("a", 1), None);
+        assert_eq!(cache.insert("b", 2), None);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+        assert_eq!(cache.peek("b"), None);
+        assert_eq!(cache.insert("a", 10), Some(("a", 1)));
+        assert_eq!(cache.insert("d", 4), Some(("c", 3)));
+        assert_eq!(cache.iter().collect::<Vec<_>>(), [(&"a", &10), (&"d", &4)]);
+        assert_eq!(cache.remove("a"), Some(10));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_compactage() {
+        let mut cache = LruRing::new(3);
+        for i in 0..3 {
+            cache.insert(i, i * 10);
+        }
+        for tour in 0..100 {
+            let cle = tour % 2;
+            *cache.get_mut(&cle).unwrap() += 1;
+        }
+        assert_eq!(cache.insert(3, 30), Some((2, 20)));
+        assert_eq!(cache.peek(&0), Some(&50));
+        assert_eq!(cache.peek(&1), Some(&60));
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), [0, 1, 3]);
+    }
+
+    #[test]
+    fn test_ensemble_sans_doublons() {
+        let mut vus = RingSet::new(3);
+        assert!(vus.insert(1));
+        assert!(vus.insert(2));
+        assert!(!vus.insert(1));
+        assert!(vus.insert(3));
+        assert!(vus.insert(4));
+        assert!(!vus.contains(&1));
+        assert!(!vus.insert_or_refresh(2));
+        assert!(vus.insert(5));
+        assert_eq!(vus.iter().copied().collect::<Vec<_>>(), [4, 2, 5]);
+        assert!(vus.remove(&4));
+        assert_eq!(vus.pop_oldest(), Some(2));
+    }
+}
@@ -0,0 +1,325 @@
+//! Buffer d'octets dont le contenu peut être lu d'un seul tenant
+//! (fonctionnalité `std`).
+//!
+//! Sous Linux avec la fonctionnalité `mirror`, le stockage est projeté deux
+//! fois de suite dans l'espace d'adressage : l'octet `i + capacité` est le même
+//! que l'octet `i`, si bien qu'un contenu qui fait le tour reste lisible d'un
+//! seul tenant, sans copie. Ailleurs, ou si la projection échoue, le stockage
+//! est un simple tableau de `capacité` octets, lu en deux tranches comme celui
+//! de [`ByteRingBuffer`](crate::ByteRingBuffer).
+
+use std::io::{self, BufRead, Read, Write};
+#[cfg(all(feature = "mirror", target_os = "linux"))]
+use std::ptr::NonNull;
+
+use crate::CircularBufferError;
+
+enum Storage {
+    // Deux projections consécutives du même fichier mémoire de `capacity`
+    // octets
+    #[cfg(all(feature = "mirror", target_os = "linux"))]
+    Mapped(NonNull<u8>),
+    // `capacity` octets, sans projection
+    Plain(Box<[u8]>),
+}
+
+/// Buffer circulaire d'octets dont [`as_contiguous_slice`](Self::as_contiguous_slice)
+/// renvoie tout le contenu en une seule tranche lorsque le stockage est
+/// projeté deux fois ([`is_mirrored`](Self::is_mirrored)).
+///
+/// Sans projection, le contenu qui fait le tour se lit avec
+/// [`as_slices`](Self::as_slices).
+///
+/// Comme [`ByteRingBuffer`](crate::ByteRingBuffer), il implémente `io::Read`,
+/// `io::BufRead` et `io::Write`, et une écriture sur un buffer plein échoue
+/// avec `io::ErrorKind::WouldBlock`.
+pub struct MirroredByteRing {
+    storage: Storage,
+    capacity: usize,
+    start: usize,
+    len: usize,
+}
+
+// SAFETY: la projection appartient au buffer seul, comme une allocation.
+unsafe impl Send for MirroredByteRing {}
+unsafe impl Sync for MirroredByteRing {}
+
+impl MirroredByteRing {
+    /// Création d'un buffer vide d'au moins `capacity` octets
+    ///
+    /// Avec la projection double, la capacité est arrondie au multiple
+    /// supérieur de la taille d'une page.
+    pub fn new(capacity: usize) -> Result<Self, CircularBufferError> {
+        if capacity == 0 {
+            return Err(CircularBufferError::ZeroCapacity);
+        }
+        #[cfg(all(feature = "mirror", target_os = "linux"))]
+        if let Some((ptr, capacity)) = map_mirrored(capacity) {
+            return Ok(Self::with_storage(Storage::Mapped(ptr), capacity));
+        }
+        Ok(Self::plain(capacity))
+    }
+
+    fn plain(capacity: usize) -> Self {
+        Self::with_storage(
+            Storage::Plain(vec![0; capacity].into_boxed_slice()),
+            capacity,
+        )
+    }
+
+    fn with_storage(storage: Storage, capacity: usize) -> Self {
+        Self {
+            storage,
+            capacity,
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Indique si le stockage est projeté deux fois, et donc si le contenu
+    /// est toujours contigu
+    pub fn is_mirrored(&self) -> bool {
+        !matches!(self.storage, Storage::Plain(_))
+    }
+
+    /// Tout le contenu, du plus ancien au plus récent octet, en une tranche
+    ///
+    /// Renvoie `None` si le stockage n'est pas projeté deux fois et que le
+    /// contenu fait le tour ; [`as_slices`](Self::as_slices) le lit alors en
+    /// deux parties.
+    pub fn as_contiguous_slice(&self) -> Option<&[u8]> {
+        let (first, second) = self.as_slices();
+        second.is_empty().then_some(first)
+    }
+
+    /// Le contenu en deux tranches, du plus ancien au plus récent octet ; la
+    /// seconde est toujours vide si le stockage est projeté deux fois
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        match &self.storage {
+            #[cfg(all(feature = "mirror", target_os = "linux"))]
+            // SAFETY: la projection couvre `2 * capacity` octets initialisés
+            // (fichier mémoire rempli de zéros à la création), et
+            // `start + len <= 2 * capacity`.
+            Storage::Mapped(ptr) => unsafe {
+                let first = std::slice::from_raw_parts(ptr.as_ptr().add(self.start), self.len);
+                (first, &[])
+            },
+            Storage::Plain(bytes) => {
+                let first = self.len.min(self.capacity - self.start);
+                (
+                    &bytes[self.start..self.start + first],
+                    &bytes[..self.len - first],
+                )
+            }
+        }
+    }
+
+    /// Retire les `n` plus anciens octets
+    pub fn consume(&mut self, n: usize) {
+        let n = n.min(self.len);
+        self.start = (self.start + n) % self.capacity;
+        self.len -= n;
+    }
+
+    /// Nombre d'octets en attente de lecture
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Nombre d'octets pouvant encore être écrits
+    pub fn free(&self) -> usize {
+        self.capacity - self.len
+    }
+
+    /// Vide complètement le buffer
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+
+    // Copie `bytes` à la position physique `at` ; la place libre doit suffire
+    fn write_at(&mut self, at: usize, bytes: &[u8]) {
+        let capacity = self.capacity;
+        match &mut self.storage {
+            #[cfg(all(feature = "mirror", target_os = "linux"))]
+            // SAFETY: `at < capacity` et `bytes.len() <= capacity` : la copie
+            // reste dans les `2 * capacity` octets projetés.
+            Storage::Mapped(ptr) => unsafe {
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr.as_ptr().add(at), bytes.len());
+            },
+            Storage::Plain(plain) => {
+                let first = bytes.len().min(capacity - at);
+                let (start, wrapped) = bytes.split_at(first);
+                plain[at..at + first].copy_from_slice(start);
+                plain[..wrapped.len()].copy_from_slice(wrapped);
+            }
+        }
+    }
+}
+
+impl Drop for MirroredByteRing {
+    fn drop(&mut self) {
+        #[cfg(all(feature = "mirror", target_os = "linux"))]
+        if let Storage::Mapped(ptr) = self.storage {
+            // SAFETY: projection de `2 * capacity` octets créée par
+            // `map_mirrored`.
+            unsafe { libc::munmap(ptr.as_ptr().cast(), 2 * self.capacity) };
+        }
+    }
+}
+
+impl Read for MirroredByteRing {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (first, second) = self.as_slices();
+        let n = buf.len().min(self.len);
+        let split = n.min(first.len());
+        buf[..split].copy_from_slice(&first[..split]);
+        buf[split..n].copy_from_slice(&second[..n - split]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for MirroredByteRing {
+    /// Renvoie tout le contenu en attente si le stockage est projeté deux
+    /// fois, sinon sa première tranche
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        MirroredByteRing::consume(self, amt);
+    }
+}
+
+impl Write for MirroredByteRing {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !buf.is_empty() && self.is_full() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let n = buf.len().min(self.free());
+        let at = (self.start + self.len) % self.capacity;
+        self.write_at(at, &buf[..n]);
+        self.len += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Réserve `2 * capacité` octets puis y projette deux fois un fichier mémoire ;
+// renvoie l'adresse et la capacité arrondie à la taille d'une page
+#[cfg(all(feature = "mirror", target_os = "linux"))]
+fn map_mirrored(capacity: usize) -> Option<(NonNull<u8>, usize)> {
+    // SAFETY: appels système sans effet sur la mémoire existante : la
+    // réservation est anonyme et les projections `MAP_FIXED` la remplacent.
+    unsafe {
+        let page = usize::try_from(libc::sysconf(libc::_SC_PAGESIZE)).ok()?;
+        let capacity = capacity.checked_next_multiple_of(page)?;
+        let total = capacity.checked_mul(2)?;
+        let fd = libc::memfd_create(c"circularbuffer".as_ptr(), libc::MFD_CLOEXEC);
+        if fd < 0 {
+            return None;
+        }
+        let reserved = libc::mmap(
+            std::ptr::null_mut(),
+            total,
+            libc::PROT_NONE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        let mut ok =
+            reserved != libc::MAP_FAILED && libc::ftruncate(fd, capacity as libc::off_t) == 0;
+        for half in [0, capacity] {
+            ok = ok
+                && libc::mmap(
+                    reserved.cast::<u8>().add(half).cast(),
+                    capacity,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED | libc::MAP_FIXED,
+                    fd,
+                    0,
+                ) != libc::MAP_FAILED;
+        }
+        libc::close(fd);
+        if !ok {
+            if reserved != libc::MAP_FAILED {
+                libc::munmap(reserved, total);
+            }
+            return None;
+        }
+        Some((NonNull::new(reserved.cast())?, capacity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier_enroulement(mut tampon: MirroredByteRing) {
+        let capacite = tampon.capacity();
+        let debut = vec![1; capacite - 2];
+        tampon.write_all(&debut).unwrap();
+        tampon.consume(capacite - 3);
+        // Le contenu fait le tour du stockage ; il ne reste contigu que si
+        // le stockage est projeté deux fois
+        assert_eq!(tampon.write(b"abcdef").unwrap(), 6);
+        let (premier, second) = tampon.as_slices();
+        assert_eq!([premier, second].concat(), b"\x01abcdef");
+        if tampon.is_mirrored() {
+            assert_eq!(tampon.as_contiguous_slice(), Some(&b"\x01abcdef"[..]));
+            assert_eq!(tampon.fill_buf().unwrap().len(), 7);
+        } else {
+            assert_eq!(tampon.as_contiguous_slice(), None);
+            assert_eq!(tampon.fill_buf().unwrap(), b"\x01ab");
+        }
+        let mut lu = [0; 4];
+        tampon.read_exact(&mut lu).unwrap();
+        assert_eq!(&lu, b"\x01abc");
+        assert_eq!(tampon.as_contiguous_slice(), Some(&b"def"[..]));
+
+        tampon.write_all(&vec![2; capacite - 3]).unwrap();
+        assert!(tampon.is_full());
+        assert_eq!(
+            tampon.write(b"x").unwrap_err().kind(),
+            io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[test]
+    fn test_contenu_contigu() {
+        let tampon = MirroredByteRing::new(100).unwrap();
+        assert!(tampon.capacity() >= 100);
+        assert_eq!(
+            tampon.is_mirrored(),
+            cfg!(all(feature = "mirror", target_os = "linux"))
+        );
+        verifier_enroulement(tampon);
+    }
+
+    #[test]
+    fn test_repli_sans_projection() {
+        let tampon = MirroredByteRing::plain(16);
+        assert!(!tampon.is_mirrored());
+        verifier_enroulement(tampon);
+        assert!(MirroredByteRing::new(0).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! Buffer circulaire sans verrou à plusieurs producteurs et plusieurs
+//! consommateurs.
+//!
+//! Chaque case porte un numéro de séquence (algorithme de D. Vyukov) : il
+//! indique aux producteurs si la case est libre pour la position visée, et aux
+//! consommateurs si l'élément de cette position est publié.
+//!
+//! Les positions sont des `usize` qui font le tour de leur domaine ; la
+//! capacité est donc arrondie à la puissance de deux supérieure, pour que la
+//! case d'une position, obtenue par le même masque que
+//! [`CircularBuffer::with_pow2_capacity`](crate::CircularBuffer::with_pow2_capacity),
+//! reste la même après ce tour, y compris sur les cibles 32 bits.
+//!
+//! Le stockage, lui, ne peut pas être celui de [`CircularBuffer`](crate::CircularBuffer) :
+//! ce dernier sait quelles cases sont initialisées grâce à ses positions de
+//! tête et de queue, modifiées sous `&mut self`. Ici, plusieurs threads
+//! réservent des positions en même temps, et seul le numéro de séquence porté
+//! par chaque case dit si son élément est écrit.
+
+use alloc::boxed::Box;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::padded::CachePadded;
+use crate::{pow2_capacity, pow2_slot};
+
+struct Slot<T> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// File bornée partagée entre plusieurs threads.
+///
+/// Aucune opération ne bloque : [`push`](Self::push) rend l'élément si la file
+/// est pleine et [`pop`](Self::pop) renvoie `None` si elle est vide. La file
+/// est partagée par référence, par exemple dans un `Arc`.
+pub struct MpmcQueue<T> {
+    buffer: Box<[Slot<T>]>,
+    // Positions des producteurs et des consommateurs, sur des lignes de cache
+    // distinctes
+    enqueue_pos: CachePadded<AtomicUsize>,
+    dequeue_pos: CachePadded<AtomicUsize>,
+}
+
+// SAFETY: une case n'est écrite que par le producteur qui a réservé sa
+// position, et lue que par le consommateur qui a réservé la même position.
+unsafe impl<T: Send> Send for MpmcQueue<T> {}
+unsafe impl<T: Send> Sync for MpmcQueue<T> {}
+
+// Raison de l'échec d'une tentative unique
+enum Failure {
+    // File pleine (ajout) ou vide (retrait)
+    Unavailable,
+    // Position prise par un autre thread
+    Contended,
+}
+
+impl<T> MpmcQueue<T> {
+    /// Création d'une file vide d'au moins `capacity` éléments, arrondie à la
+    /// puissance de deux supérieure
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0 ou si la puissance de deux dépasse
+    /// `usize`.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = pow2_capacity(capacity);
+        Self {
+            buffer: (0..capacity)
+                .map(|i| Slot {
+                    sequence: AtomicUsize::new(i),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+            enqueue_pos: CachePadded(AtomicUsize::new(0)),
+            dequeue_pos: CachePadded(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Ajout d'un élément ; si la file est pleine, l'élément est rendu dans
+    /// `Err`
+    ///
+    /// En cas de concurrence avec d'autres producteurs, la réservation est
+    /// retentée sans bloquer.
+    pub fn push(&self, item: T) -> Result<(), T> {
+        let mut item = item;
+        loop {
+            match self.attempt_push(item) {
+                Ok(()) => return Ok(()),
+                Err((rendu, Failure::Unavailable)) => return Err(rendu),
+                Err((rendu, Failure::Contended)) => item = rendu,
+            }
+        }
+    }
+
+    /// Ajout d'un élément en une seule tentative : l'élément est rendu dans
+    /// `Err` si la file est pleine ou si un autre producteur a pris la position
+    pub fn try_push(&self, item: T) -> Result<(), T> {
+        self.attempt_push(item).map_err(|(rendu, _)| rendu)
+    }
+
+    /// Retrait de l'élément le plus ancien
+    ///
+    /// En cas de concurrence avec d'autres consommateurs, la réservation est
+    /// retentée sans bloquer.
+    pub fn pop(&self) -> Option<T> {
+        loop {
+            match self.attempt_pop() {
+                Ok(item) => return Some(item),
+                Err(Failure::Unavailable) => return None,
+                Err(Failure::Contended) => {}
+            }
+        }
+    }
+
+    /// Retrait en une seule tentative : renvoie `None` si la file est vide ou
+    /// si un autre consommateur a pris la position
+    pub fn try_pop(&self) -> Option<T> {
+        self.attempt_pop().ok()
+    }
+
+    /// Nombre d'éléments dans la file, approximatif si d'autres threads y
+    /// accèdent en même temps
+    pub fn len(&self) -> usize {
+        let dequeue = self.dequeue_pos.load(Ordering::Relaxed);
+        let enqueue = self.enqueue_pos.load(Ordering::Relaxed);
+        enqueue.wrapping_sub(dequeue).min(self.capacity())
+    }
+
+    /// Vérifie si la file est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vérifie si la file est pleine
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Retourne la capacité totale de la file, une puissance de deux
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn slot(&self, position: usize) -> &Slot<T> {
+        &self.buffer[pow2_slot(position, self.capacity())]
+    }
+
+    // Réserve la position d'écriture courante puis publie l'élément
+    fn attempt_push(&self, item: T) -> Result<(), (T, Failure)> {
+        let position = self.enqueue_pos.load(Ordering::Relaxed);
+        let slot = self.slot(position);
+        // La case est libre pour `position` quand sa séquence vaut `position`
+        let diff = slot.sequence.load(Ordering::Acquire).wrapping_sub(position) as isize;
+        if diff < 0 {
+            return Err((item, Failure::Unavailable));
+        }
+        if diff > 0 || !Self::reserve(&self.enqueue_pos, position) {
+            return Err((item, Failure::Contended));
+        }
+        // SAFETY: la position est réservée, la case est libre et seul ce
+        // producteur y écrit.
+        unsafe { (*slot.value.get()).write(item) };
+        slot.sequence
+            .store(position.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    // Réserve la position de lecture courante puis libère la case
+    fn attempt_pop(&self) -> Result<T, Failure> {
+        let position = self.dequeue_pos.load(Ordering::Relaxed);
+        let slot = self.slot(position);
+        // L'élément est publié quand la séquence vaut `position + 1`
+        let diff = slot
+            .sequence
+            .load(Ordering::Acquire)
+            .wrapping_sub(position.wrapping_add(1)) as isize;
+        if diff < 0 {
+            return Err(Failure::Unavailable);
+        }
+        if diff > 0 || !Self::reserve(&self.dequeue_pos, position) {
+            return Err(Failure::Contended);
+        }
+        // SAFETY: la position est réservée et l'élément publié ; seul ce
+        // consommateur le lit.
+        let item = unsafe { (*slot.value.get()).assume_init_read() };
+        // La case redevient libre pour le tour suivant
+        slot.sequence
+            .store(position.wrapping_add(self.capacity()), Ordering::Release);
+        Ok(item)
+    }
+
+    fn reserve(counter: &AtomicUsize, position: usize) -> bool {
+        counter
+            .compare_exchange(
+                position,
+                position.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+}
+
+impl<T> Drop for MpmcQueue<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_push_pop() {
+        let file = MpmcQueue::new(2);
+        assert_eq!(file.push(1), Ok(()));
+        assert_eq!(file.try_push(2), Ok(()));
+        assert_eq!(file.push(3), Err(3));
+        assert!(file.is_full());
+        assert_eq!(file.pop(), Some(1));
+        assert_eq!(file.push(3), Ok(()));
+        assert_eq!(file.try_pop(), Some(2));
+        assert_eq!(file.pop(), Some(3));
+        assert_eq!(file.pop(), None);
+        assert!(file.is_empty());
+    }
+
+    #[test]
+    fn test_plusieurs_threads() {
+        const PAR_PRODUCTEUR: usize = 1_000;
+        let file = Arc::new(MpmcQueue::new(8));
+        let recus = Arc::new(AtomicUsize::new(0));
+        let somme = Arc::new(AtomicUsize::new(0));
+        let mut threads = Vec::new();
+        for p in 0..3 {
+            let file = Arc::clone(&file);
+            threads.push(thread::spawn(move || {
+                for i in 0..PAR_PRODUCTEUR {
+                    let mut item = p * PAR_PRODUCTEUR + i;
+                    while let Err(rendu) = file.push(item) {
+                        item = rendu;
+                        thread::yield_now();
+                    }
+                }
+            }));
+        }
+        for _ in 0..3 {
+            let (file, recus, somme) = (Arc::clone(&file), Arc::clone(&recus), Arc::clone(&somme));
+            threads.push(thread::spawn(move || {
+                while recus.load(Ordering::Relaxed) < 3 * PAR_PRODUCTEUR {
+                    match file.pop() {
+                        Some(x) => {
+                            somme.fetch_add(x, Ordering::Relaxed);
+                            recus.fetch_add(1, Ordering::Relaxed);
+                        }
+                        None => thread::yield_now(),
+                    }
+                }
+            }));
+        }
+        for t in threads {
+            t.join().unwrap();
+        }
+        let n = 3 * PAR_PRODUCTEUR;
+        assert_eq!(somme.load(Ordering::Relaxed), n * (n - 1) / 2);
+        assert!(file.is_empty());
+    }
+
+    #[test]
+    fn test_elements_restants_liberes() {
+        let jeton = Arc::new(());
+        let file = MpmcQueue::new(4);
+        for _ in 0..3 {
+            file.push(Arc::clone(&jeton)).unwrap();
+        }
+        drop(file);
+        assert_eq!(Arc::strong_count(&jeton), 1);
+    }
+
+    #[test]
+    fn test_capacite_arrondie() {
+        let file = MpmcQueue::new(3);
+        assert_eq!(file.capacity(), 4);
+        for i in 0..4 {
+            assert_eq!(file.push(i), Ok(()));
+        }
+        assert_eq!(file.push(4), Err(4));
+        assert_eq!(file.pop(), Some(0));
+    }
+}
@@ -0,0 +1,196 @@
+//! Fenêtre glissante de succès et d'échecs, pour un disjoncteur
+//! (fonctionnalité `std`).
+
+use std::time::{Duration, Instant};
+
+use crate::{CircularBuffer, Clock, SystemClock};
+
+/// Garde le résultat des `capacity` derniers appels, et éventuellement
+/// seulement ceux des `window` dernières secondes, pour en calculer le taux
+/// d'échec.
+///
+/// Le nombre d'échecs stockés est tenu à jour à chaque enregistrement, ce qui
+/// rend les requêtes en O(1) sans fenêtre de temps.
+pub struct RollingOutcomeWindow<C = SystemClock> {
+    // Instant et succès de chaque appel, du plus ancien au plus récent
+    outcomes: CircularBuffer<(Instant, bool)>,
+    window: Option<Duration>,
+    // Nombre d'échecs parmi `outcomes`, expirés compris
+    failures: usize,
+    clock: C,
+}
+
+impl RollingOutcomeWindow {
+    /// Création d'une fenêtre gardant les `capacity` derniers résultats
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            outcomes: CircularBuffer::new(capacity),
+            window: None,
+            failures: 0,
+            clock: SystemClock,
+        }
+    }
+
+    /// Création d'une fenêtre gardant au plus `capacity` résultats, chacun
+    /// pendant au plus `window`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn with_duration(capacity: usize, window: Duration) -> Self {
+        Self {
+            window: Some(window),
+            ..Self::new(capacity)
+        }
+    }
+}
+
+impl<C: Clock> RollingOutcomeWindow<C> {
+    /// Remplace l'horloge de la fenêtre, par exemple par une
+    /// [`ManualClock`](crate::ManualClock)
+    pub fn with_clock<D: Clock>(self, clock: D) -> RollingOutcomeWindow<D> {
+        RollingOutcomeWindow {
+            outcomes: self.outcomes,
+            window: self.window,
+            failures: self.failures,
+            clock,
+        }
+    }
+
+    /// Enregistre le résultat d'un appel, réussi si `success` est vrai
+    pub fn record(&mut self, success: bool) {
+        let now = self.clock.now();
+        for _ in 0..self.expired_at(now) {
+            if let Some((_, false)) = self.outcomes.pop() {
+                self.failures -= 1;
+            }
+        }
+        if let Some((_, false)) = self.outcomes.push((now, success)) {
+            self.failures -= 1;
+        }
+        if !success {
+            self.failures += 1;
+        }
+    }
+
+    /// Enregistre un appel réussi
+    pub fn record_success(&mut self) {
+        self.record(true);
+    }
+
+    /// Enregistre un appel en échec
+    pub fn record_failure(&mut self) {
+        self.record(false);
+    }
+
+    /// Enregistre le résultat d'un appel selon la variante de `result`
+    pub fn record_result<T, E>(&mut self, result: &Result<T, E>) {
+        self.record(result.is_ok());
+    }
+
+    /// Retourne le nombre de résultats dans la fenêtre
+    pub fn len(&self) -> usize {
+        self.outcomes.len() - self.expired_at(self.clock.now())
+    }
+
+    /// Vérifie si la fenêtre ne contient aucun résultat
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne le nombre d'échecs dans la fenêtre
+    pub fn failures(&self) -> usize {
+        let expired = self.expired_at(self.clock.now());
+        let expired_failures = self
+            .outcomes
+            .iter()
+            .take(expired)
+            .filter(|(_, success)| !success)
+            .count();
+        self.failures - expired_failures
+    }
+
+    /// Retourne le nombre de succès dans la fenêtre
+    pub fn successes(&self) -> usize {
+        self.len() - self.failures()
+    }
+
+    /// Retourne la proportion d'échecs dans la fenêtre, entre 0 et 1, ou
+    /// `None` si elle est vide
+    pub fn failure_rate(&self) -> Option<f64> {
+        match self.len() {
+            0 => None,
+            len => Some(self.failures() as f64 / len as f64),
+        }
+    }
+
+    /// Retourne le nombre maximal de résultats gardés
+    pub fn capacity(&self) -> usize {
+        self.outcomes.capacity()
+    }
+
+    /// Retourne la durée de la fenêtre, s'il y en a une
+    pub fn window(&self) -> Option<Duration> {
+        self.window
+    }
+
+    /// Oublie tous les résultats, par exemple à la refermeture du disjoncteur
+    pub fn reset(&mut self) {
+        self.outcomes.clear();
+        self.failures = 0;
+    }
+
+    // Nombre de résultats sortis de la fenêtre de temps, tous en tête
+    fn expired_at(&self, now: Instant) -> usize {
+        match self.window {
+            Some(window) => self
+                .outcomes
+                .partition_point(|(t, _)| now.saturating_duration_since(*t) >= window),
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ManualClock;
+
+    #[test]
+    fn test_derniers_appels() {
+        let mut fenetre = RollingOutcomeWindow::new(4);
+        assert_eq!(fenetre.failure_rate(), None);
+        fenetre.record_failure();
+        fenetre.record_result(&Ok::<(), ()>(()));
+        fenetre.record_result(&Err::<(), ()>(()));
+        fenetre.record_success();
+        assert_eq!(fenetre.failure_rate(), Some(0.5));
+        fenetre.record_success();
+        assert_eq!(fenetre.failures(), 1);
+        assert_eq!(fenetre.successes(), 3);
+        assert_eq!(fenetre.failure_rate(), Some(0.25));
+    }
+
+    #[test]
+    fn test_dernieres_secondes() {
+        let horloge = ManualClock::new();
+        let mut fenetre = RollingOutcomeWindow::with_duration(10, Duration::from_secs(30))
+            .with_clock(horloge.clone());
+        fenetre.record_failure();
+        fenetre.record_failure();
+        horloge.advance(Duration::from_secs(20));
+        fenetre.record_success();
+        assert_eq!(fenetre.failures(), 2);
+        horloge.advance(Duration::from_secs(10));
+        assert_eq!(fenetre.len(), 1);
+        assert_eq!(fenetre.failure_rate(), Some(0.0));
+        fenetre.record_failure();
+        assert_eq!(fenetre.failure_rate(), Some(0.5));
+        horloge.advance(Duration::from_secs(60));
+        assert!(fenetre.is_empty());
+    }
+}
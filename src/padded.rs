@@ -0,0 +1,39 @@
+//! Alignement sur une ligne de cache des compteurs partagés entre threads.
+
+use core::ops::{Deref, DerefMut};
+
+/// Valeur placée seule sur sa ligne de cache de 64 octets.
+///
+/// Le producteur et le consommateur modifient chacun leur compteur : sur une
+/// même ligne, chaque écriture invaliderait la copie de l'autre cœur.
+#[repr(align(64))]
+pub(crate) struct CachePadded<T>(pub(crate) T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_une_ligne_par_valeur() {
+        struct Compteurs {
+            _a: CachePadded<usize>,
+            _b: CachePadded<usize>,
+        }
+        assert_eq!(core::mem::align_of::<CachePadded<u8>>(), 64);
+        assert_eq!(core::mem::size_of::<Compteurs>(), 128);
+    }
+}
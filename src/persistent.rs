@@ -0,0 +1,478 @@
+//! Buffer circulaire d'enregistrements stocké dans un fichier de taille fixe
+//! (fonctionnalité `std`).
+//!
+//! Format, entiers en petit-boutiste :
+//!
+//! | Champ         | Taille    | Contenu                                    |
+//! |---------------|-----------|--------------------------------------------|
+//! | signature     | 8 octets  | `CBUFLOG\0`                                |
+//! | version       | 4 octets  | `2`                                        |
+//! | taille        | 4 octets  | taille d'une case en octets                |
+//! | capacité      | 8 octets  | nombre de cases                            |
+//! | début         | 8 octets  | position du plus ancien enregistrement     |
+//! | fin           | 8 octets  | position du prochain enregistrement        |
+//! | cases         | variable  | `capacité` cases de `taille` octets        |
+//!
+//! Les positions croissent sans fin ; la case utilisée est
+//! `position % capacité`. Une case contient :
+//!
+//! | Champ         | Taille    | Contenu                                    |
+//! |---------------|-----------|--------------------------------------------|
+//! | marqueur      | 8 octets  | position de l'enregistrement plus 1        |
+//! | longueur      | 4 octets  | longueur de l'enregistrement               |
+//! | CRC           | 4 octets  | CRC-32 du marqueur, de la longueur et de   |
+//! |               |           | l'enregistrement                           |
+//! | données       | variable  | écrites par [`BinaryElement::write_to`]    |
+//!
+//! # Reprise après interruption
+//!
+//! Un ajout écrit d'abord la case, puis les positions de l'en-tête. À
+//! l'ouverture, les cases sont vérifiées : les enregistrements validés après
+//! la dernière mise à jour de l'en-tête sont retrouvés grâce à leur marqueur,
+//! puis seule est gardée la plus longue suite de cases valides se terminant
+//! par le dernier enregistrement validé. Une case à moitié écrite (CRC faux)
+//! est donc écartée avec tout ce qui la précède. Un ajout interrompu ne peut
+//! abîmer que la case qu'il écrivait, c'est-à-dire au pire le plus ancien
+//! enregistrement qu'il allait écraser.
+//!
+//! Par défaut, les écritures ne sont pas forcées sur le disque : elles
+//! survivent à l'arrêt brutal du processus, mais pas à une coupure de
+//! courant. Pour cela, appeler [`sync`](PersistentRingBuffer::sync) ou
+//! activer [`with_sync`](PersistentRingBuffer::with_sync).
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::{BinaryElement, CircularBufferError};
+
+const MAGIC: &[u8; 8] = b"CBUFLOG\0";
+const VERSION: u32 = 2;
+const HEADER_LEN: u64 = 40;
+// Position des champs « début » et « fin » dans l'en-tête
+const POSITIONS_OFFSET: u64 = 24;
+// Marqueur, longueur et CRC en tête de chaque case
+const SLOT_HEADER_LEN: usize = 16;
+
+/// Buffer circulaire persistant : les enregistrements survivent au
+/// redémarrage du processus et les plus anciens sont écrasés lorsque le
+/// fichier est plein.
+///
+/// Chaque enregistrement occupe une case de taille fixe, choisie à la
+/// création ; un élément dont l'encodage ne tient pas dans une case est
+/// refusé.
+pub struct PersistentRingBuffer<T> {
+    file: File,
+    record_size: u32,
+    capacity: u64,
+    start: u64,
+    end: u64,
+    sync: bool,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T: BinaryElement> PersistentRingBuffer<T> {
+    /// Crée (ou écrase) le fichier `path` avec `capacity` cases de
+    /// `record_size` octets, en-tête de case (16 octets) compris
+    pub fn create(path: impl AsRef<Path>, capacity: u64, record_size: u32) -> io::Result<Self> {
+        if capacity == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                CircularBufferError::ZeroCapacity,
+            ));
+        }
+        if (record_size as usize) <= SLOT_HEADER_LEN {
+            return Err(invalid_input("Taille de case trop petite."));
+        }
+        let file_len = capacity
+            .checked_mul(u64::from(record_size))
+            .and_then(|data| data.checked_add(HEADER_LEN))
+            .ok_or_else(|| invalid_input("Capacité trop grande."))?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let mut header = Vec::with_capacity(HEADER_LEN as usize);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        header.extend_from_slice(&record_size.to_le_bytes());
+        header.extend_from_slice(&capacity.to_le_bytes());
+        header.extend_from_slice(&[0; 16]);
+        file.write_all(&header)?;
+        file.set_len(file_len)?;
+        Ok(Self {
+            file,
+            record_size,
+            capacity,
+            start: 0,
+            end: 0,
+            sync: false,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Ouvre un fichier créé par [`create`](Self::create) et retrouve son
+    /// contenu, en écartant les écritures interrompues
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let mut header = [0; HEADER_LEN as usize];
+        file.read_exact(&mut header)?;
+        if &header[..8] != MAGIC {
+            return Err(invalid_data("Signature de journal invalide."));
+        }
+        if read_u32(&header[8..]) != VERSION {
+            return Err(invalid_data("Version de journal non prise en charge."));
+        }
+        let record_size = read_u32(&header[12..]);
+        let capacity = read_u64(&header[16..]);
+        let start = read_u64(&header[24..]);
+        let end = read_u64(&header[32..]);
+        if capacity == 0 || (record_size as usize) <= SLOT_HEADER_LEN {
+            return Err(invalid_data("Dimensions de journal invalides."));
+        }
+        if start > end || end - start > capacity {
+            return Err(invalid_data("Positions de journal incohérentes."));
+        }
+        let needed = capacity
+            .checked_mul(u64::from(record_size))
+            .and_then(|data| data.checked_add(HEADER_LEN));
+        let file_len = file.metadata()?.len();
+        if needed.is_none_or(|needed| needed > file_len) {
+            return Err(invalid_data("Fichier de journal tronqué."));
+        }
+        let mut buffer = Self {
+            file,
+            record_size,
+            capacity,
+            start,
+            end,
+            sync: false,
+            _marker: PhantomData,
+        };
+        buffer.recover()?;
+        Ok(buffer)
+    }
+
+    /// Ouvre le fichier `path` s'il existe, sinon le crée avec les dimensions
+    /// données
+    pub fn open_or_create(
+        path: impl AsRef<Path>,
+        capacity: u64,
+        record_size: u32,
+    ) -> io::Result<Self> {
+        match Self::open(path.as_ref()) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Self::create(path, capacity, record_size)
+            }
+            result => result,
+        }
+    }
+
+    /// Force l'écriture sur le disque à chaque modification (`push`, `pop`,
+    /// `clear`), avant de rendre la main
+    ///
+    /// Chaque modification survit alors à une coupure de courant, au prix
+    /// d'un appel à `sync_data` par opération.
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Ajout d'un enregistrement, en écrasant le plus ancien si le fichier
+    /// est plein
+    ///
+    /// Renvoie une erreur `InvalidInput` si l'enregistrement ne tient pas dans
+    /// une case.
+    pub fn push(&mut self, item: &T) -> io::Result<()> {
+        let mut record = vec![0; SLOT_HEADER_LEN];
+        item.write_to(&mut record)?;
+        if record.len() > self.record_size as usize {
+            return Err(invalid_input("Enregistrement plus grand qu'une case."));
+        }
+        let len = (record.len() - SLOT_HEADER_LEN) as u32;
+        record[..8].copy_from_slice(&(self.end + 1).to_le_bytes());
+        record[8..12].copy_from_slice(&len.to_le_bytes());
+        let crc = slot_crc(&record);
+        record[12..16].copy_from_slice(&crc.to_le_bytes());
+        self.file
+            .seek(SeekFrom::Start(self.slot_offset(self.end)))?;
+        self.file.write_all(&record)?;
+        if self.is_full() {
+            self.start += 1;
+        }
+        self.end += 1;
+        self.write_positions()
+    }
+
+    /// Retrait de l'enregistrement le plus ancien
+    pub fn pop(&mut self) -> io::Result<Option<T>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let item = self.read_at(self.start)?;
+        self.start += 1;
+        self.write_positions()?;
+        Ok(Some(item))
+    }
+
+    /// Relit tous les enregistrements, du plus ancien au plus récent
+    pub fn read_all(&mut self) -> io::Result<Vec<T>> {
+        (self.start..self.end)
+            .map(|pos| self.read_at(pos))
+            .collect()
+    }
+
+    /// Vide le buffer
+    pub fn clear(&mut self) -> io::Result<()> {
+        self.start = self.end;
+        self.write_positions()
+    }
+
+    /// Force l'écriture des données sur le disque
+    ///
+    /// Sans [`with_sync`](Self::with_sync), rien d'autre ne le fait.
+    pub fn sync(&self) -> io::Result<()> {
+        self.file.sync_data()
+    }
+
+    /// Retourne le nombre d'enregistrements stockés
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity
+    }
+
+    /// Retourne le nombre de cases du fichier
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Retourne la taille d'une case en octets, en-tête de case compris
+    pub fn record_size(&self) -> u32 {
+        self.record_size
+    }
+
+    fn slot_offset(&self, position: u64) -> u64 {
+        HEADER_LEN + (position % self.capacity) * u64::from(self.record_size)
+    }
+
+    fn read_at(&mut self, position: u64) -> io::Result<T> {
+        let record = self
+            .read_record(position)?
+            .ok_or_else(|| invalid_data("Enregistrement corrompu."))?;
+        T::read_from(&mut &record[SLOT_HEADER_LEN..])
+    }
+
+    // Relit la case de `position` ; `None` si elle ne contient pas un
+    // enregistrement validé pour cette position
+    fn read_record(&mut self, position: u64) -> io::Result<Option<Vec<u8>>> {
+        let mut record = vec![0; self.record_size as usize];
+        self.file
+            .seek(SeekFrom::Start(self.slot_offset(position)))?;
+        self.file.read_exact(&mut record)?;
+        let len = read_u32(&record[8..]) as usize;
+        if read_u64(&record) != position + 1 || len > record.len() - SLOT_HEADER_LEN {
+            return Ok(None);
+        }
+        record.truncate(SLOT_HEADER_LEN + len);
+        if read_u32(&record[12..]) != slot_crc(&record) {
+            return Ok(None);
+        }
+        Ok(Some(record))
+    }
+
+    fn is_committed(&mut self, position: u64) -> io::Result<bool> {
+        Ok(self.read_record(position)?.is_some())
+    }
+
+    // Retrouve la plus longue suite d'enregistrements valides se terminant
+    // par la dernière case validée, puis réécrit les positions si besoin
+    fn recover(&mut self) -> io::Result<()> {
+        let mut end = self.end;
+        // Enregistrements validés après la dernière mise à jour de l'en-tête ;
+        // une case ne portant qu'un marqueur, la boucle s'arrête au plus tard
+        // après un tour complet
+        while self.is_committed(end)? {
+            end += 1;
+        }
+        let oldest = end.saturating_sub(self.capacity).max(self.start);
+        // Écriture interrompue en fin de journal
+        while end > oldest && !self.is_committed(end - 1)? {
+            end -= 1;
+        }
+        let mut first = end;
+        while first > oldest && self.is_committed(first - 1)? {
+            first -= 1;
+        }
+        if (first, end) != (self.start, self.end) {
+            self.start = first;
+            self.end = end;
+            self.write_positions()?;
+        }
+        Ok(())
+    }
+
+    fn write_positions(&mut self) -> io::Result<()> {
+        let mut positions = [0; 16];
+        positions[..8].copy_from_slice(&self.start.to_le_bytes());
+        positions[8..].copy_from_slice(&self.end.to_le_bytes());
+        self.file.seek(SeekFrom::Start(POSITIONS_OFFSET))?;
+        self.file.write_all(&positions)?;
+        if self.sync {
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+// CRC de la case, hors champ CRC lui-même
+fn slot_crc(record: &[u8]) -> u32 {
+    let crc = crc32_update(!0, &record[..12]);
+    !crc32_update(crc, &record[SLOT_HEADER_LEN..])
+}
+
+// Table du CRC-32 (polynôme réfléchi 0xEDB88320, celui de zlib)
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        crc = CRC_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn chemin(nom: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("circularbuffer-{}-{}", std::process::id(), nom))
+    }
+
+    // Modifie directement des octets du fichier, comme une écriture interrompue
+    fn ecrire_octets(chemin: &Path, position: u64, octets: &[u8]) {
+        let mut fichier = OpenOptions::new().write(true).open(chemin).unwrap();
+        fichier.seek(SeekFrom::Start(position)).unwrap();
+        fichier.write_all(octets).unwrap();
+    }
+
+    #[test]
+    fn test_survit_a_la_reouverture() {
+        let chemin = chemin("journal");
+        {
+            let mut journal = PersistentRingBuffer::<String>::create(&chemin, 3, 48).unwrap();
+            for evenement in ["démarrage", "connexion", "requête", "arrêt"] {
+                journal.push(&evenement.to_string()).unwrap();
+            }
+            assert!(journal.is_full());
+        }
+        let mut journal = PersistentRingBuffer::<String>::open(&chemin).unwrap();
+        assert_eq!(
+            journal.read_all().unwrap(),
+            ["connexion", "requête", "arrêt"]
+        );
+        assert_eq!(journal.pop().unwrap().as_deref(), Some("connexion"));
+        drop(journal);
+
+        let mut journal = PersistentRingBuffer::<String>::open_or_create(&chemin, 10, 8).unwrap();
+        assert_eq!(journal.capacity(), 3);
+        assert_eq!(journal.read_all().unwrap(), ["requête", "arrêt"]);
+        std::fs::remove_file(chemin).unwrap();
+    }
+
+    #[test]
+    fn test_enregistrement_trop_grand() {
+        let chemin = chemin("trop-grand");
+        assert!(PersistentRingBuffer::<u64>::create(&chemin, 2, 16).is_err());
+        let mut journal = PersistentRingBuffer::<u128>::create(&chemin, 2, 24).unwrap();
+        let erreur = journal.push(&1).unwrap_err();
+        assert_eq!(erreur.kind(), io::ErrorKind::InvalidInput);
+        assert!(journal.is_empty());
+
+        let mut journal = PersistentRingBuffer::<u64>::create(&chemin, 2, 24)
+            .unwrap()
+            .with_sync(true);
+        journal.push(&7).unwrap();
+        assert_eq!(journal.pop().unwrap(), Some(7));
+        assert_eq!(journal.pop().unwrap(), None);
+        std::fs::remove_file(chemin).unwrap();
+    }
+
+    #[test]
+    fn test_reprise_apres_interruption() {
+        let chemin = chemin("reprise");
+        let case = |position: u64| HEADER_LEN + position * 24;
+        let mut journal = PersistentRingBuffer::<u64>::create(&chemin, 4, 24).unwrap();
+        for i in 0..3 {
+            journal.push(&i).unwrap();
+        }
+        drop(journal);
+
+        // Dernier ajout validé mais en-tête non mis à jour
+        ecrire_octets(&chemin, POSITIONS_OFFSET + 8, &2u64.to_le_bytes());
+        let journal = PersistentRingBuffer::<u64>::open(&chemin).unwrap();
+        assert_eq!(journal.len(), 3);
+        drop(journal);
+
+        // Ajout interrompu au milieu de la dernière case
+        ecrire_octets(&chemin, case(2) + SLOT_HEADER_LEN as u64, &[0xFF; 4]);
+        let mut journal = PersistentRingBuffer::<u64>::open(&chemin).unwrap();
+        assert_eq!(journal.read_all().unwrap(), [0, 1]);
+        journal.push(&3).unwrap();
+        journal.push(&4).unwrap();
+        journal.push(&5).unwrap();
+        drop(journal);
+
+        // Écrasement interrompu du plus ancien enregistrement
+        ecrire_octets(&chemin, case(1) + SLOT_HEADER_LEN as u64, &[0xFF; 4]);
+        let mut journal = PersistentRingBuffer::<u64>::open(&chemin).unwrap();
+        assert_eq!(journal.read_all().unwrap(), [3, 4, 5]);
+        std::fs::remove_file(chemin).unwrap();
+    }
+}
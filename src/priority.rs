@@ -0,0 +1,109 @@
+//! Buffer qui écrase l'élément de plus faible priorité plutôt que le plus
+//! ancien.
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+/// Buffer de capacité fixe dont chaque élément porte une priorité.
+///
+/// Tant qu'il reste de la place, il se comporte comme un [`CircularBuffer`].
+/// Une fois plein, un ajout écrase l'élément de plus faible priorité, le plus
+/// ancien en cas d'égalité ; un élément de priorité strictement plus faible
+/// que tous les autres est rendu sans être ajouté.
+pub struct PriorityCircularBuffer<T, P> {
+    // Priorité et élément, du plus ancien au plus récent
+    items: CircularBuffer<(P, T)>,
+}
+
+impl<T, P: Ord> PriorityCircularBuffer<T, P> {
+    /// Création d'un buffer vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: CircularBuffer::with_policy(capacity, OverflowPolicy::Reject),
+        }
+    }
+
+    /// Ajout d'un élément de priorité `priority` ; l'élément écrasé, ou
+    /// l'élément ajouté s'il est le moins prioritaire, est rendu
+    pub fn push(&mut self, item: T, priority: P) -> Option<T> {
+        let mut evicted = None;
+        if self.items.is_full() {
+            // `min_by_key` garde le premier minimum, donc le plus ancien
+            let (index, (lowest, _)) = self.items.iter().enumerate().min_by_key(|(_, (p, _))| p)?;
+            if priority < *lowest {
+                return Some(item);
+            }
+            evicted = self.items.remove_at(index).map(|(_, old)| old);
+        }
+        // Il reste de la place : l'ajout ne peut pas être refusé
+        let _ = self.items.push((priority, item));
+        evicted
+    }
+
+    /// Retrait de l'élément le plus ancien, quelle que soit sa priorité
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop().map(|(_, item)| item)
+    }
+
+    /// Retourne une référence à l'élément le plus ancien
+    pub fn peek(&self) -> Option<&T> {
+        self.items.peek().map(|(_, item)| item)
+    }
+
+    /// Parcourt les éléments et leur priorité, du plus ancien au plus récent
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &P)> {
+        self.items.iter().map(|(priority, item)| (item, priority))
+    }
+
+    /// Retourne le nombre d'éléments
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.items.is_full()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Vide le buffer
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_ecrase_la_plus_faible_priorite() {
+        let mut alertes = PriorityCircularBuffer::new(3);
+        assert_eq!(alertes.push("debug 1", 0), None);
+        assert_eq!(alertes.push("critique", 9), None);
+        assert_eq!(alertes.push("debug 2", 0), None);
+        assert_eq!(alertes.push("info", 1), Some("debug 1"));
+        assert_eq!(alertes.push("debug 3", 0), Some("debug 2"));
+        assert_eq!(alertes.push("trace", -1), Some("trace"));
+        assert_eq!(alertes.push("critique 2", 9), Some("debug 3"));
+        assert_eq!(alertes.push("critique 3", 9), Some("info"));
+        assert_eq!(
+            alertes.iter().map(|(a, _)| *a).collect::<Vec<_>>(),
+            ["critique", "critique 2", "critique 3"]
+        );
+        assert_eq!(alertes.pop(), Some("critique"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Limiteur de débit à fenêtre glissante (fonctionnalité `std`).
+
+use std::time::{Duration, Instant};
+
+use crate::{CircularBuffer, Clock, SystemClock};
+
+/// Autorise au plus `max_events` événements par fenêtre glissante de durée
+/// `window`.
+///
+/// L'instant de chaque événement accepté est gardé dans un buffer de
+/// `max_events` cases : un nouvel événement est accepté si le buffer n'est
+/// pas plein une fois retirés les instants sortis de la fenêtre.
+pub struct RateLimiter<C = SystemClock> {
+    // Instants des événements acceptés, du plus ancien au plus récent
+    events: CircularBuffer<Instant>,
+    window: Duration,
+    clock: C,
+}
+
+impl RateLimiter {
+    /// Création d'un limiteur acceptant `max_events` événements par `window`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `max_events` vaut 0.
+    pub fn new(max_events: usize, window: Duration) -> Self {
+        Self {
+            events: CircularBuffer::new(max_events),
+            window,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<C: Clock> RateLimiter<C> {
+    /// Remplace l'horloge du limiteur, par exemple par une
+    /// [`ManualClock`](crate::ManualClock)
+    pub fn with_clock<D: Clock>(self, clock: D) -> RateLimiter<D> {
+        RateLimiter {
+            events: self.events,
+            window: self.window,
+            clock,
+        }
+    }
+
+    /// Vérifie si un événement serait accepté maintenant, sans l'enregistrer
+    pub fn check(&self) -> bool {
+        self.remaining() > 0
+    }
+
+    /// Enregistre un événement s'il reste de la place dans la fenêtre et
+    /// retourne `true`, sinon retourne `false` sans rien enregistrer
+    pub fn try_acquire(&mut self) -> bool {
+        let now = self.clock.now();
+        let expired = self.expired_at(now);
+        for _ in 0..expired {
+            self.events.pop();
+        }
+        if self.events.is_full() {
+            return false;
+        }
+        self.events.push(now);
+        true
+    }
+
+    /// Retourne le nombre d'événements encore acceptés dans la fenêtre courante
+    pub fn remaining(&self) -> usize {
+        let active = self.events.len() - self.expired_at(self.clock.now());
+        self.max_events() - active
+    }
+
+    /// Retourne le nombre maximal d'événements par fenêtre
+    pub fn max_events(&self) -> usize {
+        self.events.capacity()
+    }
+
+    /// Retourne la durée de la fenêtre
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Oublie les événements enregistrés
+    pub fn reset(&mut self) {
+        self.events.clear();
+    }
+
+    // Nombre d'événements sortis de la fenêtre à `now`, tous en tête
+    fn expired_at(&self, now: Instant) -> usize {
+        self.events
+            .partition_point(|t| now.saturating_duration_since(*t) >= self.window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ManualClock;
+
+    #[test]
+    fn test_fenetre_glissante() {
+        let horloge = ManualClock::new();
+        let mut limiteur = RateLimiter::new(2, Duration::from_secs(10)).with_clock(horloge.clone());
+        assert!(limiteur.try_acquire());
+        horloge.advance(Duration::from_secs(4));
+        assert!(limiteur.try_acquire());
+        assert!(!limiteur.check());
+        assert!(!limiteur.try_acquire());
+        horloge.advance(Duration::from_secs(6));
+        assert_eq!(limiteur.remaining(), 1);
+        assert!(limiteur.try_acquire());
+        assert!(!limiteur.try_acquire());
+        horloge.advance(Duration::from_secs(10));
+        assert_eq!(limiteur.remaining(), 2);
+    }
+}
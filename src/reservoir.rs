@@ -0,0 +1,126 @@
+//! Échantillon aléatoire uniforme d'un flux, par échantillonnage en réservoir.
+
+use crate::eviction::XorShift;
+use crate::{CircularBuffer, Iter};
+
+/// Buffer de capacité fixe qui garde un échantillon uniforme de tous les
+/// éléments reçus, et pas seulement des plus récents.
+///
+/// Une fois plein, le `n`-ième élément reçu remplace une case tirée au hasard
+/// avec la probabilité `capacity / n` (algorithme R) : chaque élément du flux
+/// a alors la même chance d'être dans l'échantillon. L'ordre des éléments ne
+/// reflète plus l'ordre d'arrivée.
+pub struct ReservoirBuffer<T> {
+    sample: CircularBuffer<T>,
+    // Nombre d'éléments reçus depuis la création ou le dernier `clear`
+    seen: u64,
+    rng: XorShift,
+}
+
+impl<T> ReservoirBuffer<T> {
+    /// Création d'un échantillon vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_seed(capacity, 0x9e37_79b9_7f4a_7c15)
+    }
+
+    /// Création d'un échantillon dont le tirage dépend de `seed`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn with_seed(capacity: usize, seed: u64) -> Self {
+        Self {
+            sample: CircularBuffer::new(capacity),
+            seen: 0,
+            rng: XorShift::new(seed),
+        }
+    }
+
+    /// Propose un élément à l'échantillon ; l'élément remplacé, ou l'élément
+    /// proposé s'il n'est pas retenu, est rendu
+    pub fn push(&mut self, item: T) -> Option<T> {
+        self.seen += 1;
+        if !self.sample.is_full() {
+            self.sample.push(item);
+            return None;
+        }
+        let slot = self.rng.below(self.seen) as usize;
+        if slot < self.sample.len() {
+            Some(core::mem::replace(&mut self.sample[slot], item))
+        } else {
+            Some(item)
+        }
+    }
+
+    /// Retourne le nombre d'éléments reçus, retenus ou non
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Parcourt l'échantillon
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.sample.iter()
+    }
+
+    /// Retourne le nombre d'éléments de l'échantillon
+    pub fn len(&self) -> usize {
+        self.sample.len()
+    }
+
+    /// Vérifie si l'échantillon est vide
+    pub fn is_empty(&self) -> bool {
+        self.sample.is_empty()
+    }
+
+    /// Retourne la taille maximale de l'échantillon
+    pub fn capacity(&self) -> usize {
+        self.sample.capacity()
+    }
+
+    /// Vide l'échantillon et remet à zéro le nombre d'éléments reçus
+    pub fn clear(&mut self) {
+        self.sample.clear();
+        self.seen = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remplissage_puis_remplacement() {
+        let mut echantillon = ReservoirBuffer::new(3);
+        for i in 0..3 {
+            assert_eq!(echantillon.push(i), None);
+        }
+        assert!(echantillon.push(3).is_some());
+        assert_eq!(echantillon.len(), 3);
+        assert_eq!(echantillon.seen(), 4);
+        echantillon.clear();
+        assert!(echantillon.is_empty());
+        assert_eq!(echantillon.seen(), 0);
+    }
+
+    #[test]
+    fn test_echantillon_uniforme() {
+        // Chacun des 10 éléments doit être retenu environ 2 fois sur 10
+        let mut retenus = [0u32; 10];
+        for graine in 0..2000 {
+            let mut echantillon = ReservoirBuffer::with_seed(2, graine);
+            for i in 0..10 {
+                echantillon.push(i);
+            }
+            for &i in echantillon.iter() {
+                retenus[i] += 1;
+            }
+        }
+        for compte in retenus {
+            assert!((300..500).contains(&compte), "{:?}", retenus);
+        }
+    }
+}
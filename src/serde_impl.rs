@@ -0,0 +1,104 @@
+//! Sérialisation avec `serde` (fonctionnalité `serde`).
+//!
+//! Un buffer est représenté par sa capacité et la séquence de ses éléments,
+//! du plus ancien au plus récent :
+//! `{"capacity": 3, "items": [1, 2]}`.
+
+use alloc::vec::Vec;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{CircularBuffer, CircularBufferError};
+
+#[derive(Serialize)]
+#[serde(rename = "CircularBuffer")]
+struct BufferRef<'a, T> {
+    capacity: usize,
+    items: Items<'a, T>,
+}
+
+// Éléments sérialisés comme une séquence dans l'ordre logique
+struct Items<'a, T>(&'a CircularBuffer<T>);
+
+impl<T: Serialize> Serialize for Items<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "CircularBuffer")]
+struct BufferRepr<T> {
+    capacity: usize,
+    items: Vec<T>,
+}
+
+impl<T: Serialize> Serialize for CircularBuffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BufferRef {
+            capacity: self.capacity(),
+            items: Items(self),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// La politique du buffer obtenu est [`OverflowPolicy::Overwrite`](crate::OverflowPolicy).
+/// Une capacité qui ne peut pas être allouée est refusée.
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CircularBuffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = BufferRepr::<T>::deserialize(deserializer)?;
+        if repr.capacity == 0 {
+            return Err(D::Error::custom(CircularBufferError::ZeroCapacity));
+        }
+        if repr.items.len() > repr.capacity {
+            return Err(D::Error::custom(format_args!(
+                "Le buffer contient {} éléments pour une capacité de {}.",
+                repr.items.len(),
+                repr.capacity
+            )));
+        }
+        let mut buffer = CircularBuffer::try_allocate(repr.capacity).ok_or_else(|| {
+            D::Error::custom(format_args!(
+                "Impossible d'allouer un buffer de capacité {}.",
+                repr.capacity
+            ))
+        })?;
+        buffer.extend(repr.items);
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CircularBuffer;
+
+    #[test]
+    fn test_aller_retour_json() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend([1, 2, 3, 4]);
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(json, r#"{"capacity":3,"items":[2,3,4]}"#);
+
+        let relu: CircularBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(relu.capacity(), 3);
+        assert_eq!(relu, buffer);
+    }
+
+    #[test]
+    fn test_capacite_validee() {
+        let trop_plein =
+            serde_json::from_str::<CircularBuffer<i32>>(r#"{"capacity":1,"items":[1,2]}"#);
+        assert!(trop_plein
+            .unwrap_err()
+            .to_string()
+            .contains("capacité de 1"));
+        let nul = serde_json::from_str::<CircularBuffer<i32>>(r#"{"capacity":0,"items":[]}"#);
+        assert!(nul.is_err());
+        let enorme = serde_json::from_str::<CircularBuffer<i32>>(
+            r#"{"capacity":1152921504606846976,"items":[]}"#,
+        );
+        assert!(enorme.unwrap_err().to_string().contains("allouer"));
+    }
+}
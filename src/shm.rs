@@ -0,0 +1,339 @@
+//! Buffer circulaire placé dans une zone de mémoire partagée entre processus
+//! (fonctionnalité `shm`, Unix uniquement).
+//!
+//! Le fichier projeté commence par un en-tête de taille fixe, suivi de la zone
+//! de données contenant `capacité` enregistrements de taille fixe :
+//!
+//! | Champ          | Taille    | Contenu                                |
+//! |----------------|-----------|----------------------------------------|
+//! | signature      | 8 octets  | `CBUFSHM\0`                            |
+//! | état           | 4 octets  | `0` en cours d'initialisation, `1` prêt|
+//! | version        | 4 octets  | `1`                                    |
+//! | taille         | 8 octets  | taille d'un enregistrement             |
+//! | capacité       | 8 octets  | nombre d'enregistrements               |
+//! | `head`         | 8 octets  | nombre total d'ajouts (atomique)       |
+//! | `tail`         | 8 octets  | nombre total de retraits (atomique)    |
+//! | données        | variable  | à partir de l'octet 64                 |
+//!
+//! Un processus ajoute et un autre retire, comme avec [`spsc`](crate::spsc).
+//! L'état « prêt » est écrit en dernier par [`SharedRing::create`] : si le
+//! créateur s'interrompt pendant l'initialisation, [`SharedRing::open`] refuse
+//! le fichier et un nouvel appel à `create` le réinitialise.
+
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of};
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use crate::CircularBufferError;
+
+const MAGIC: [u8; 8] = *b"CBUFSHM\0";
+const VERSION: u32 = 1;
+const READY: u32 = 1;
+// Début de la zone de données, qui reste alignée pour tout enregistrement
+// d'alignement au plus 64
+const DATA_OFFSET: usize = 64;
+
+#[repr(C)]
+struct Header {
+    magic: [u8; 8],
+    state: AtomicU32,
+    version: u32,
+    record_size: u64,
+    capacity: u64,
+    head: AtomicU64,
+    tail: AtomicU64,
+}
+
+const _: () = assert!(size_of::<Header>() <= DATA_OFFSET);
+
+/// Type pouvant être échangé tel quel entre processus.
+///
+/// # Safety
+///
+/// Toute suite d'octets de la taille du type doit être une valeur valide, et
+/// le type ne doit contenir ni pointeur ni référence : l'autre processus peut
+/// écrire n'importe quel contenu. Les types `#[repr(C)]` composés d'entiers
+/// et de flottants conviennent.
+pub unsafe trait Record: Copy + 'static {}
+
+macro_rules! impl_record {
+    ($($t:ty),*) => {$(
+        // SAFETY: tous les motifs de bits sont valides pour ce type numérique.
+        unsafe impl Record for $t {}
+    )*};
+}
+
+impl_record!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// SAFETY: un tableau d'enregistrements est valide dès que chaque élément l'est.
+unsafe impl<T: Record, const N: usize> Record for [T; N] {}
+
+/// Buffer circulaire d'enregistrements de type `T` dans un fichier projeté en
+/// mémoire partagée.
+pub struct SharedRing<T: Record> {
+    map: NonNull<u8>,
+    map_len: usize,
+    capacity: usize,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: la zone projetée n'appartient à aucun thread ; les accès passent par
+// les compteurs atomiques de l'en-tête.
+unsafe impl<T: Record + Send> Send for SharedRing<T> {}
+
+impl<T: Record> SharedRing<T> {
+    /// Crée (ou réinitialise) le fichier `path` pour contenir `capacity`
+    /// enregistrements, vide
+    pub fn create(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        if capacity == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                CircularBufferError::ZeroCapacity,
+            ));
+        }
+        check_alignment::<T>()?;
+        let map_len = capacity
+            .checked_mul(size_of::<T>())
+            .and_then(|data| data.checked_add(DATA_OFFSET))
+            .ok_or_else(|| invalid_input("Capacité trop grande."))?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        // Le fichier tronqué puis agrandi est rempli de zéros : l'état vaut
+        // 0 jusqu'à la fin de l'initialisation.
+        file.set_len(map_len as u64)?;
+        let map = map_file(&file, map_len)?;
+
+        let header = map.as_ptr().cast::<Header>();
+        // SAFETY: la projection couvre l'en-tête, aligné sur une page.
+        unsafe {
+            ptr::addr_of_mut!((*header).magic).write(MAGIC);
+            ptr::addr_of_mut!((*header).version).write(VERSION);
+            ptr::addr_of_mut!((*header).record_size).write(size_of::<T>() as u64);
+            ptr::addr_of_mut!((*header).capacity).write(capacity as u64);
+            (*header).head.store(0, Ordering::Relaxed);
+            (*header).tail.store(0, Ordering::Relaxed);
+            (*header).state.store(READY, Ordering::Release);
+        }
+        Ok(Self {
+            map,
+            map_len,
+            capacity,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Ouvre un fichier créé par [`create`](Self::create), éventuellement par
+    /// un autre processus
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        check_alignment::<T>()?;
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let map_len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| invalid_data("Fichier trop grand."))?;
+        if map_len < DATA_OFFSET {
+            return Err(invalid_data("En-tête de buffer partagé incomplet."));
+        }
+        let map = map_file(&file, map_len)?;
+        // La capacité est renseignée une fois l'en-tête vérifié ; en cas
+        // d'erreur, `Drop` libère la projection.
+        let mut ring = Self {
+            map,
+            map_len,
+            capacity: 0,
+            _marker: PhantomData,
+        };
+
+        let header = ring.header();
+        if header.state.load(Ordering::Acquire) != READY {
+            return Err(invalid_data("Buffer partagé non initialisé."));
+        }
+        if header.magic != MAGIC {
+            return Err(invalid_data("Signature de buffer partagé invalide."));
+        }
+        if header.version != VERSION {
+            return Err(invalid_data(
+                "Version de buffer partagé non prise en charge.",
+            ));
+        }
+        if header.record_size != size_of::<T>() as u64 {
+            return Err(invalid_data("Taille d'enregistrement différente."));
+        }
+        let capacity =
+            usize::try_from(header.capacity).map_err(|_| invalid_data("Capacité trop grande."))?;
+        let needed = capacity
+            .checked_mul(size_of::<T>())
+            .and_then(|data| data.checked_add(DATA_OFFSET));
+        if capacity == 0 || needed.is_none_or(|needed| needed > map_len) {
+            return Err(invalid_data(
+                "Capacité incompatible avec la taille du fichier.",
+            ));
+        }
+        ring.capacity = capacity;
+        Ok(ring)
+    }
+
+    /// Ajout d'un enregistrement ; si le buffer est plein, il est rendu dans
+    /// `Err`
+    ///
+    /// Un seul processus doit ajouter des enregistrements.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        let header = self.header();
+        let head = header.head.load(Ordering::Relaxed);
+        let tail = header.tail.load(Ordering::Acquire);
+        if head.wrapping_sub(tail) >= self.capacity as u64 {
+            return Err(item);
+        }
+        // SAFETY: la case "head" est libre et seul le producteur y écrit.
+        unsafe { self.slot(head).write(item) };
+        header.head.store(head.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Retrait de l'enregistrement le plus ancien
+    ///
+    /// Un seul processus doit retirer des enregistrements.
+    pub fn pop(&mut self) -> Option<T> {
+        let header = self.header();
+        let tail = header.tail.load(Ordering::Relaxed);
+        let head = header.head.load(Ordering::Acquire);
+        if tail == head {
+            return None;
+        }
+        // SAFETY: la case "tail" a été publiée par le producteur ; `Record`
+        // garantit que son contenu est une valeur valide.
+        let item = unsafe { self.slot(tail).read() };
+        header.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(item)
+    }
+
+    /// Nombre d'enregistrements en attente
+    pub fn len(&self) -> usize {
+        let header = self.header();
+        let tail = header.tail.load(Ordering::Acquire);
+        let head = header.head.load(Ordering::Acquire);
+        (head.wrapping_sub(tail) as usize).min(self.capacity)
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn header(&self) -> &Header {
+        // SAFETY: la projection commence par un en-tête aligné sur une page.
+        unsafe { self.map.cast::<Header>().as_ref() }
+    }
+
+    fn slot(&self, position: u64) -> *mut T {
+        let index = (position % self.capacity as u64) as usize;
+        // SAFETY: `open` et `create` ont vérifié que la zone de données
+        // contient `capacity` enregistrements.
+        unsafe {
+            self.map
+                .as_ptr()
+                .add(DATA_OFFSET + index * size_of::<T>())
+                .cast::<T>()
+        }
+    }
+}
+
+impl<T: Record> Drop for SharedRing<T> {
+    fn drop(&mut self) {
+        // SAFETY: la projection a été créée par `map_file` avec cette taille.
+        unsafe { libc::munmap(self.map.as_ptr().cast(), self.map_len) };
+    }
+}
+
+fn map_file(file: &File, len: usize) -> io::Result<NonNull<u8>> {
+    // SAFETY: projection partagée d'un fichier ouvert en lecture et écriture,
+    // de la taille du fichier.
+    let ptr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    NonNull::new(ptr.cast()).ok_or_else(|| io::Error::from(io::ErrorKind::Other))
+}
+
+fn check_alignment<T>() -> io::Result<()> {
+    if align_of::<T>() > DATA_OFFSET {
+        return Err(invalid_input("Alignement d'enregistrement trop grand."));
+    }
+    Ok(())
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn chemin(nom: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("circularbuffer-{}-{}", std::process::id(), nom))
+    }
+
+    #[test]
+    fn test_echange_entre_projections() {
+        let chemin = chemin("echange");
+        let mut producteur = SharedRing::<[u32; 2]>::create(&chemin, 2).unwrap();
+        let mut consommateur = SharedRing::<[u32; 2]>::open(&chemin).unwrap();
+        assert_eq!(consommateur.capacity(), 2);
+        assert_eq!(producteur.push([1, 10]), Ok(()));
+        assert_eq!(producteur.push([2, 20]), Ok(()));
+        assert_eq!(producteur.push([3, 30]), Err([3, 30]));
+        assert_eq!(consommateur.len(), 2);
+        assert_eq!(consommateur.pop(), Some([1, 10]));
+        assert_eq!(producteur.push([3, 30]), Ok(()));
+        assert_eq!(consommateur.pop(), Some([2, 20]));
+        assert_eq!(consommateur.pop(), Some([3, 30]));
+        assert_eq!(consommateur.pop(), None);
+        std::fs::remove_file(chemin).unwrap();
+    }
+
+    #[test]
+    fn test_fichier_invalide() {
+        let chemin = chemin("invalide");
+        // Fichier d'un créateur interrompu avant la fin de l'initialisation
+        std::fs::write(&chemin, [0u8; 128]).unwrap();
+        assert!(SharedRing::<u64>::open(&chemin).is_err());
+
+        drop(SharedRing::<u64>::create(&chemin, 4).unwrap());
+        assert_eq!(SharedRing::<u64>::open(&chemin).unwrap().capacity(), 4);
+        assert!(SharedRing::<u32>::open(&chemin).is_err());
+        assert!(SharedRing::<u64>::create(&chemin, 0).is_err());
+        std::fs::remove_file(chemin).unwrap();
+    }
+}
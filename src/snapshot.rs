@@ -0,0 +1,160 @@
+//! Sauvegarde et restauration du contenu d'un [`CircularBuffer`], et copies
+//! figées partagées entre threads.
+
+use alloc::boxed::Box;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use crate::CircularBuffer;
+
+/// Contenu et positions d'un buffer à un instant donné, obtenus par
+/// [`CircularBuffer::snapshot`].
+///
+/// Un instantané est indépendant du buffer : il peut être cloné pour revenir
+/// plusieurs fois au même point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<T> {
+    items: Vec<T>,
+    // Position absolue du plus ancien élément
+    tail: u64,
+}
+
+impl<T> Snapshot<T> {
+    /// Retourne le nombre d'éléments sauvegardés
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Vérifie si l'instantané ne contient aucun élément
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Copie immuable et contiguë du contenu d'un buffer, obtenue par
+/// [`CircularBuffer::freeze`].
+///
+/// Elle se lit comme une tranche, du plus ancien au plus récent élément.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct FrozenBuffer<T> {
+    items: Box<[T]>,
+    tail: u64,
+}
+
+impl<T> FrozenBuffer<T> {
+    /// Retourne les éléments, du plus ancien au plus récent
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Retourne le numéro de séquence du premier élément ; celui de
+    /// l'élément d'indice `i` est ce numéro plus `i`
+    pub fn first_seq(&self) -> u64 {
+        self.tail
+    }
+}
+
+impl<T> Deref for FrozenBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<'a, T> IntoIterator for &'a FrozenBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T: Clone> CircularBuffer<T> {
+    /// Sauvegarde le contenu du buffer pour y revenir avec
+    /// [`restore`](Self::restore)
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot {
+            items: self.iter().cloned().collect(),
+            tail: self.tail,
+        }
+    }
+
+    /// Copie le contenu dans un [`FrozenBuffer`] partagé
+    ///
+    /// La copie est faite une seule fois : cloner l'`Arc` obtenu ne coûte
+    /// qu'un compteur, et les autres threads la lisent sans verrou pendant que
+    /// le buffer continue de recevoir des éléments.
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn freeze(&self) -> Arc<FrozenBuffer<T>> {
+        Arc::new(FrozenBuffer {
+            items: self.iter().cloned().collect(),
+            tail: self.tail,
+        })
+    }
+}
+
+impl<T> CircularBuffer<T> {
+    /// Remplace le contenu du buffer par celui de `snapshot`
+    ///
+    /// Les positions absolues reviennent aussi à celles de la sauvegarde : les
+    /// numéros de séquence et les [`Cursor`](crate::Cursor) obtenus avant
+    /// restent valides. La capacité augmente si l'instantané ne tient pas ; la
+    /// politique, les fermetures et les statistiques ne changent pas.
+    pub fn restore(&mut self, snapshot: Snapshot<T>) {
+        // Les seuils ne voient que le résultat, pas le buffer vidé entre-temps
+        let watermarks = self.watermarks.take();
+        self.clear();
+        self.watermarks = watermarks;
+        if snapshot.len() > self.capacity() {
+            self.relocate(snapshot.len());
+        }
+        self.tail = snapshot.tail;
+        self.head = snapshot.tail;
+        for item in snapshot.items {
+            self.write_head(item);
+        }
+        self.check_watermarks();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CircularBuffer;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_retour_arriere() {
+        let mut fenetre = CircularBuffer::new(4);
+        fenetre.extend_from_slice(b"abc");
+        let point = fenetre.snapshot();
+        let curseur = fenetre.cursor();
+        fenetre.pop();
+        fenetre.extend_from_slice(b"defg");
+        fenetre.restore(point.clone());
+        assert_eq!(fenetre.iter().copied().collect::<Vec<_>>(), b"abc");
+        assert_eq!(curseur.remaining(&fenetre), Some(3));
+        fenetre.extend_from_slice(b"xy");
+        fenetre.restore(point);
+        assert_eq!(fenetre.len(), 3);
+        assert_eq!(fenetre.push_seq(b'z'), Ok(3));
+    }
+
+    #[test]
+    fn test_copie_figee() {
+        let mut buffer = CircularBuffer::new(3);
+        buffer.extend([1, 2, 3, 4]);
+        let figee = buffer.freeze();
+        let lecteur = std::thread::spawn({
+            let figee = alloc::sync::Arc::clone(&figee);
+            move || figee.iter().sum::<i32>()
+        });
+        buffer.push(5);
+        assert_eq!(lecteur.join().unwrap(), 9);
+        assert_eq!(figee.as_slice(), [2, 3, 4]);
+        assert_eq!(figee.first_seq(), 1);
+    }
+}
@@ -0,0 +1,367 @@
+//! Buffer circulaire sans verrou à un producteur et un consommateur.
+//!
+//! [`channel`] renvoie deux moitiés : le [`Producer`] ajoute des éléments et le
+//! [`Consumer`] les retire, chacun pouvant vivre sur un thread différent. Les
+//! positions d'écriture (`head`) et de lecture (`tail`) sont des compteurs
+//! atomiques qui parcourent `0..2 × capacité` : la case utilisée est la
+//! position modulo la capacité, et leur écart distingue un buffer plein d'un
+//! buffer vide. Les compteurs ne débordent donc jamais, quelle que soit la
+//! largeur de `usize`.
+//!
+//! Abandonner l'une des moitiés ferme le buffer : le consommateur retire les
+//! éléments restants puis reçoit [`CircularBufferError::Closed`], et le
+//! producteur voit ses ajouts refusés.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::padded::CachePadded;
+use crate::CircularBufferError;
+
+// État partagé entre les deux moitiés ; chaque compteur, écrit par une seule
+// moitié, occupe sa propre ligne de cache
+struct Shared<T> {
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+    // L'une des moitiés a été abandonnée
+    closed: AtomicBool,
+}
+
+// SAFETY: chaque case n'est accédée que par une seule moitié à la fois : le
+// producteur écrit les cases libres, le consommateur lit les cases occupées.
+// Aucune méthode prenant `&self` ne prête d'élément, donc partager une moitié
+// entre threads ne partage jamais de `&T`.
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        self.distance(head, tail)
+    }
+
+    // Nombre de positions de `tail` à `head`, tous deux dans `0..2 × capacité`
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if head >= tail {
+            head - tail
+        } else {
+            head + 2 * self.capacity() - tail
+        }
+    }
+
+    // Position suivant `position`, ramenée à 0 après `2 × capacité - 1`
+    fn advance(&self, position: usize) -> usize {
+        if position + 1 == 2 * self.capacity() {
+            0
+        } else {
+            position + 1
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    fn close(&self) {
+        // Publie aussi les derniers ajouts du producteur
+        self.closed.store(true, Ordering::Release);
+    }
+
+    fn slot(&self, position: usize) -> *mut MaybeUninit<T> {
+        let index = match position.checked_sub(self.capacity()) {
+            Some(index) => index,
+            None => position,
+        };
+        self.buffer[index].get()
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let mut tail = *self.tail.get_mut();
+        while tail != head {
+            // SAFETY: les cases entre "tail" et "head" sont occupées et plus
+            // aucune moitié n'y accède.
+            unsafe { (*self.slot(tail)).assume_init_drop() };
+            tail = self.advance(tail);
+        }
+    }
+}
+
+/// Crée un buffer de capacité `capacity` et renvoie ses deux moitiés
+///
+/// # Panics
+///
+/// Panique si `capacity` vaut 0 ou dépasse `usize::MAX / 2`.
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity > 0, "La taille du buffer doit être positive.");
+    assert!(capacity <= usize::MAX / 2, "Capacité trop grande.");
+    let shared = Arc::new(Shared {
+        buffer: (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        head: CachePadded(AtomicUsize::new(0)),
+        tail: CachePadded(AtomicUsize::new(0)),
+        closed: AtomicBool::new(false),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+/// Moitié du buffer qui ajoute des éléments.
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Producer<T> {
+    /// Ajout d'un élément ; si le buffer est plein ou si le consommateur a
+    /// été abandonné, l'élément est rendu dans `Err`
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        if self.shared.distance(head, tail) == self.shared.capacity() || self.shared.is_closed() {
+            return Err(item);
+        }
+        // SAFETY: la case "head" est libre et seul le producteur y écrit.
+        unsafe { (*self.shared.slot(head)).write(item) };
+        self.shared
+            .head
+            .store(self.shared.advance(head), Ordering::Release);
+        Ok(())
+    }
+
+    /// Nombre d'éléments en attente
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vérifie si le buffer est plein
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Ferme le buffer : le consommateur retire encore les éléments en attente,
+    /// puis reçoit [`CircularBufferError::Closed`]
+    ///
+    /// Équivaut à abandonner le producteur.
+    pub fn close(self) {
+        drop(self);
+    }
+
+    /// Vérifie si le consommateur a été abandonné
+    pub fn is_closed(&self) -> bool {
+        self.shared.is_closed()
+    }
+}
+
+impl<T> Drop for Producer<T> {
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+/// Moitié du buffer qui retire des éléments.
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Consumer<T> {
+    /// Retrait de l'élément le plus ancien
+    pub fn pop(&mut self) -> Option<T> {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        let head = self.shared.head.load(Ordering::Acquire);
+        if tail == head {
+            return None;
+        }
+        // SAFETY: la case "tail" a été publiée par le producteur et seul le
+        // consommateur la lit.
+        let item = unsafe { (*self.shared.slot(tail)).assume_init_read() };
+        self.shared
+            .tail
+            .store(self.shared.advance(tail), Ordering::Release);
+        Some(item)
+    }
+
+    /// Retrait de l'élément le plus ancien, en distinguant un buffer
+    /// momentanément vide ([`CircularBufferError::Empty`]) d'un buffer vide
+    /// dont le producteur a été fermé ([`CircularBufferError::Closed`])
+    pub fn try_pop(&mut self) -> Result<T, CircularBufferError> {
+        // Lire l'état avant de retirer : un producteur fermé ensuite n'a pas
+        // pu ajouter d'élément que `pop` n'aurait pas vu
+        let closed = self.shared.is_closed();
+        match self.pop() {
+            Some(item) => Ok(item),
+            None if closed => Err(CircularBufferError::Closed),
+            None => Err(CircularBufferError::Empty),
+        }
+    }
+
+    /// Retourne une référence au prochain élément à être retiré sans le supprimer
+    ///
+    /// L'emprunt est exclusif : le consommateur peut être partagé entre
+    /// threads (il est `Sync` dès que `T` est `Send`), et deux threads ne
+    /// doivent pas tenir en même temps une référence à un élément qui n'est
+    /// pas `Sync`.
+    ///
+    /// ```compile_fail
+    /// let (_producteur, consommateur) = circularbuffer::spsc::channel::<u8>(1);
+    /// let partage = &consommateur;
+    /// partage.peek();
+    /// ```
+    pub fn peek(&mut self) -> Option<&T> {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        let head = self.shared.head.load(Ordering::Acquire);
+        if tail == head {
+            return None;
+        }
+        // SAFETY: la case reste occupée tant que le consommateur, emprunté
+        // ici, ne la retire pas.
+        Some(unsafe { (*self.shared.slot(tail)).assume_init_ref() })
+    }
+
+    /// Nombre d'éléments en attente
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retourne la capacité totale du buffer
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// Vérifie si le producteur a été fermé ou abandonné ; des éléments
+    /// peuvent encore être en attente
+    pub fn is_closed(&self) -> bool {
+        self.shared.is_closed()
+    }
+}
+
+impl<T> Drop for Consumer<T> {
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_push_pop() {
+        let (mut producteur, mut consommateur) = channel(2);
+        assert_eq!(producteur.push(1), Ok(()));
+        assert_eq!(producteur.push(2), Ok(()));
+        assert_eq!(producteur.push(3), Err(3));
+        assert!(producteur.is_full());
+        assert_eq!(consommateur.peek(), Some(&1));
+        assert_eq!(consommateur.pop(), Some(1));
+        assert_eq!(producteur.push(3), Ok(()));
+        assert_eq!(consommateur.pop(), Some(2));
+        assert_eq!(consommateur.pop(), Some(3));
+        assert_eq!(consommateur.pop(), None);
+        assert!(consommateur.is_empty());
+    }
+
+    #[test]
+    fn test_deux_threads() {
+        let (mut producteur, mut consommateur) = channel(16);
+        let envoi = thread::spawn(move || {
+            for i in 0..10_000u32 {
+                let mut item = i;
+                while let Err(rendu) = producteur.push(item) {
+                    item = rendu;
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut attendu = 0;
+        while attendu < 10_000 {
+            match consommateur.pop() {
+                Some(x) => {
+                    assert_eq!(x, attendu);
+                    attendu += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        envoi.join().unwrap();
+    }
+
+    #[test]
+    fn test_fermeture() {
+        let (mut producteur, mut consommateur) = channel(4);
+        producteur.push(1).unwrap();
+        assert_eq!(consommateur.try_pop(), Ok(1));
+        assert_eq!(consommateur.try_pop(), Err(CircularBufferError::Empty));
+        producteur.push(2).unwrap();
+        producteur.close();
+        assert!(consommateur.is_closed());
+        assert_eq!(consommateur.try_pop(), Ok(2));
+        assert_eq!(consommateur.try_pop(), Err(CircularBufferError::Closed));
+
+        let (mut producteur, consommateur) = channel(4);
+        drop(consommateur);
+        assert!(producteur.is_closed());
+        assert_eq!(producteur.push(1), Err(1));
+    }
+
+    #[test]
+    fn test_elements_restants_liberes() {
+        let jeton = Arc::new(());
+        let (mut producteur, consommateur) = channel(4);
+        for _ in 0..3 {
+            producteur.push(Arc::clone(&jeton)).unwrap();
+        }
+        drop(producteur);
+        assert_eq!(Arc::strong_count(&jeton), 4);
+        drop(consommateur);
+        assert_eq!(Arc::strong_count(&jeton), 1);
+    }
+
+    #[test]
+    fn test_capacite_quelconque_sur_plusieurs_tours() {
+        let (mut producteur, mut consommateur) = channel(3);
+        for i in 0..20 {
+            producteur.push(i).unwrap();
+            producteur.push(i + 100).unwrap();
+            assert_eq!(consommateur.len(), 2);
+            assert_eq!(consommateur.pop(), Some(i));
+            assert_eq!(consommateur.pop(), Some(i + 100));
+        }
+        for i in 0..3 {
+            producteur.push(i).unwrap();
+        }
+        assert!(producteur.is_full());
+        assert_eq!(producteur.push(3), Err(3));
+    }
+}
@@ -0,0 +1,104 @@
+//! Compteurs d'activité d'un [`CircularBuffer`](crate::CircularBuffer).
+
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Statistiques renvoyées par [`CircularBuffer::stats`](crate::CircularBuffer::stats).
+///
+/// Les compteurs couvrent toute la vie du buffer, ou la période écoulée
+/// depuis [`reset_stats`](crate::CircularBuffer::reset_stats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct BufferStats {
+    /// Nombre d'éléments présents au moment de l'appel.
+    pub len: usize,
+    /// Capacité au moment de l'appel.
+    pub capacity: usize,
+    /// Éléments ajoutés.
+    pub pushes: u64,
+    /// Éléments retirés par un consommateur.
+    pub pops: u64,
+    /// Éléments écrasés pour faire de la place à un nouvel ajout.
+    pub overwrites: u64,
+    /// Ajouts refusés parce que le buffer était plein.
+    pub rejected: u64,
+    /// Plus grand nombre d'éléments atteint.
+    pub high_water_mark: usize,
+}
+
+impl BufferStats {
+    // Compteurs d'un buffer neuf, utilisables dans une fonction `const`
+    pub(crate) const ZERO: Self = Self {
+        len: 0,
+        capacity: 0,
+        pushes: 0,
+        pops: 0,
+        overwrites: 0,
+        rejected: 0,
+        high_water_mark: 0,
+    };
+
+    /// Formate les statistiques au format texte de Prometheus, chaque métrique
+    /// étant nommée `{prefix}_...`
+    pub fn to_prometheus(&self, prefix: &str) -> String {
+        let metrics: [(&str, &str, &str, u64); 7] = [
+            (
+                "length",
+                "gauge",
+                "Nombre d'éléments présents.",
+                self.len as u64,
+            ),
+            (
+                "capacity",
+                "gauge",
+                "Capacité du buffer.",
+                self.capacity as u64,
+            ),
+            (
+                "high_water_mark",
+                "gauge",
+                "Plus grand nombre d'éléments atteint.",
+                self.high_water_mark as u64,
+            ),
+            ("pushes_total", "counter", "Éléments ajoutés.", self.pushes),
+            ("pops_total", "counter", "Éléments retirés.", self.pops),
+            (
+                "overwrites_total",
+                "counter",
+                "Éléments écrasés.",
+                self.overwrites,
+            ),
+            (
+                "rejected_total",
+                "counter",
+                "Ajouts refusés.",
+                self.rejected,
+            ),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            // L'écriture dans une `String` n'échoue jamais
+            let _ = write!(
+                out,
+                "# HELP {prefix}_{name} {help}\n# TYPE {prefix}_{name} {kind}\n{prefix}_{name} {value}\n"
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CircularBuffer;
+
+    #[test]
+    fn test_format_prometheus() {
+        let mut buffer = CircularBuffer::new(2);
+        buffer.extend([1, 2, 3]);
+        let texte = buffer.stats().to_prometheus("journal");
+        assert!(texte.contains("# TYPE journal_length gauge\njournal_length 2\n"));
+        assert!(texte.contains("# TYPE journal_overwrites_total counter\n"));
+        assert!(texte.contains("\njournal_overwrites_total 1\n"));
+        assert_eq!(texte.lines().count(), 21);
+    }
+}
@@ -0,0 +1,170 @@
+//! Stockages sur lesquels un [`CircularBuffer`] peut fonctionner.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+
+use crate::CircularBuffer;
+
+/// Cases dans lesquelles un [`CircularBuffer`] range ses éléments.
+///
+/// Le buffer suit lui-même les cases initialisées : le stockage ne fournit
+/// que la mémoire. Il peut être alloué sur le tas (`Box`, `Vec`), être un
+/// tableau de taille fixe, ou une tranche empruntée, par exemple à une
+/// variable `static`. Seuls les stockages qui savent s'allouer
+/// ([`allocate`](Self::allocate)) permettent de changer la capacité.
+///
+/// # Safety
+///
+/// [`slots`](Self::slots) et [`slots_mut`](Self::slots_mut) doivent toujours
+/// retourner les mêmes cases, de la même longueur, et ne jamais les modifier
+/// en dehors du buffer : le buffer lit sans vérification les cases qu'il a
+/// initialisées.
+pub unsafe trait Storage<T> {
+    /// Retourne les cases du stockage
+    fn slots(&self) -> &[MaybeUninit<T>];
+
+    /// Retourne les cases du stockage, modifiables
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>];
+
+    /// Alloue un nouveau stockage de `capacity` cases, si ce type le permet
+    fn allocate(capacity: usize) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        let _ = capacity;
+        None
+    }
+}
+
+// SAFETY: la tranche allouée ne change jamais de longueur.
+unsafe impl<T> Storage<T> for Box<[MaybeUninit<T>]> {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+
+    fn allocate(capacity: usize) -> Option<Self> {
+        Some(Box::new_uninit_slice(capacity))
+    }
+}
+
+/// La longueur du `Vec` est la capacité du buffer ; sa réserve n'est pas
+/// utilisée.
+// SAFETY: le buffer n'a pas accès au `Vec` lui-même, qui ne peut donc pas
+// changer de longueur.
+unsafe impl<T> Storage<T> for Vec<MaybeUninit<T>> {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+
+    fn allocate(capacity: usize) -> Option<Self> {
+        let mut slots = Vec::with_capacity(capacity);
+        slots.resize_with(capacity, MaybeUninit::uninit);
+        Some(slots)
+    }
+}
+
+// SAFETY: la tranche est empruntée de façon exclusive pendant toute la vie
+// du buffer.
+unsafe impl<T> Storage<T> for &mut [MaybeUninit<T>] {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+// SAFETY: un tableau a toujours `N` cases ; le déplacer déplace aussi les
+// éléments, que le buffer repère par leur indice.
+unsafe impl<T, const N: usize> Storage<T> for [MaybeUninit<T>; N] {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+impl<'a, T> CircularBuffer<T, &'a mut [MaybeUninit<T>]> {
+    /// Création d'un buffer sur des cases fournies par l'appelant, par
+    /// exemple une variable `static` ou une zone d'arène
+    ///
+    /// Le buffer travaille sur place et n'alloue jamais, sauf pour les
+    /// fermetures d'éviction et de seuil ; sa capacité est la longueur de
+    /// `slots` et ne change pas. Les éléments encore présents sont libérés
+    /// lorsqu'il est abandonné, et les cases redeviennent disponibles.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `slots` est vide.
+    pub fn from_slice_storage(slots: &'a mut [MaybeUninit<T>]) -> Self {
+        Self::from_storage(slots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CircularBuffer, CircularBufferError};
+    use alloc::vec::Vec;
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn test_meme_logique_sur_chaque_stockage() {
+        let mut cases = [const { MaybeUninit::uninit() }; 3];
+        let tranche = CircularBuffer::from_storage(&mut cases[..]);
+        let tableau = CircularBuffer::from_storage([const { MaybeUninit::uninit() }; 3]);
+        let vecteur =
+            CircularBuffer::from_storage(Vec::from_iter((0..3).map(|_| MaybeUninit::uninit())));
+        fn remplir<S: crate::Storage<i32>>(mut buffer: CircularBuffer<i32, S>) -> Vec<i32> {
+            buffer.extend_from_slice(&[1, 2, 3, 4]);
+            assert_eq!(buffer.push(5), Some(2));
+            buffer.iter().copied().collect()
+        }
+        assert_eq!(remplir(tranche), [3, 4, 5]);
+        assert_eq!(remplir(tableau), [3, 4, 5]);
+        assert_eq!(remplir(vecteur), [3, 4, 5]);
+    }
+
+    #[test]
+    fn test_capacite_fixe() {
+        let mut cases = [const { MaybeUninit::uninit() }; 2];
+        let mut buffer = CircularBuffer::from_storage(&mut cases[..]);
+        buffer.push(String::from("a"));
+        buffer.push(String::from("b"));
+        assert_eq!(buffer.resize(4), Err(CircularBufferError::FixedCapacity));
+        buffer.shrink_to_fit();
+        assert_eq!(buffer.len(), 2);
+
+        let mut vecteur =
+            CircularBuffer::from_storage(Vec::from_iter((0..2).map(|_| MaybeUninit::uninit())));
+        vecteur.extend([1, 2]);
+        assert_eq!(vecteur.resize(4), Ok(()));
+        assert_eq!(vecteur.capacity(), 4);
+    }
+
+    #[test]
+    fn test_cases_fournies() {
+        let mut cases = [const { MaybeUninit::uninit() }; 4];
+        let adresse = cases.as_ptr() as *const u8;
+        {
+            let mut buffer = CircularBuffer::from_slice_storage(&mut cases);
+            buffer.extend(1..=6u8);
+            assert_eq!(buffer, [3, 4, 5, 6]);
+            assert_eq!(buffer.as_slices().0.as_ptr(), adresse.wrapping_add(2));
+        }
+        let mut buffer = CircularBuffer::from_slice_storage(&mut cases);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.try_push(7), Ok(()));
+    }
+}
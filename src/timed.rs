@@ -0,0 +1,295 @@
+//! Buffer dont les éléments expirent après une durée (fonctionnalité `std`).
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+/// Source de l'heure utilisée par un [`TimedCircularBuffer`].
+pub trait Clock {
+    /// Retourne l'instant présent
+    fn now(&self) -> Instant;
+}
+
+/// Horloge du système, utilisée par défaut.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Horloge qui n'avance que sur demande, pour des tests reproductibles.
+///
+/// Les clones partagent le même instant : on en confie un au buffer et on
+/// garde l'autre pour le faire avancer.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Création d'une horloge arrêtée sur l'instant présent
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Fait avancer l'horloge de `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Buffer qui ne garde que les éléments ajoutés depuis moins de `ttl`, et
+/// éventuellement au plus `capacity` éléments.
+///
+/// Chaque ajout est daté par l'horloge `C` ; [`push_with_ttl`](Self::push_with_ttl)
+/// donne à un élément sa propre durée de vie. Les éléments expirés sont retirés
+/// du stockage au prochain ajout ou par [`evict_expired`](Self::evict_expired) ;
+/// entre-temps, [`len`](Self::len) et [`iter`](Self::iter) les ignorent déjà.
+/// Avec une capacité, le plus ancien est écrasé lorsque le buffer est plein,
+/// même s'il n'a pas expiré : la première des deux limites atteinte s'applique.
+pub struct TimedCircularBuffer<T, C = SystemClock> {
+    // Éléments et échéance de leur expiration, du plus ancien au plus récent
+    items: CircularBuffer<(Deadline, T)>,
+    ttl: Duration,
+    capacity: Option<usize>,
+    // Faux dès qu'une échéance précède une échéance déjà stockée
+    sorted: bool,
+    clock: C,
+}
+
+// Instant d'expiration d'un élément ; une durée de vie qui dépasse ce que
+// représente `Instant` (par exemple `Duration::MAX`) n'expire jamais
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Deadline {
+    At(Instant),
+    Never,
+}
+
+impl Deadline {
+    fn is_expired(self, now: Instant) -> bool {
+        matches!(self, Deadline::At(at) if at <= now)
+    }
+}
+
+impl<T> TimedCircularBuffer<T> {
+    /// Création d'un buffer vide, sans limite de taille, dont les éléments
+    /// vivent `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            items: CircularBuffer::with_policy(16, OverflowPolicy::Grow),
+            ttl,
+            capacity: None,
+            sorted: true,
+            clock: SystemClock,
+        }
+    }
+
+    /// Création d'un buffer vide gardant au plus `capacity` éléments, chacun
+    /// pendant au plus `ttl`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn with_capacity(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            items: CircularBuffer::new(capacity),
+            ttl,
+            capacity: Some(capacity),
+            sorted: true,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<T, C: Clock> TimedCircularBuffer<T, C> {
+    /// Remplace l'horloge du buffer, par exemple par une [`ManualClock`]
+    pub fn with_clock<D: Clock>(self, clock: D) -> TimedCircularBuffer<T, D> {
+        TimedCircularBuffer {
+            items: self.items,
+            ttl: self.ttl,
+            capacity: self.capacity,
+            sorted: self.sorted,
+            clock,
+        }
+    }
+
+    /// Retourne la durée de vie des éléments
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Retourne le nombre maximal d'éléments, s'il y en a un
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Ajout d'un élément, après avoir retiré les éléments expirés
+    ///
+    /// Si le buffer est plein, le plus ancien élément est écrasé et rendu.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        self.push_with_ttl(item, self.ttl)
+    }
+
+    /// Ajout d'un élément qui expire après `ttl` au lieu de la durée commune
+    ///
+    /// Le plus ancien élément reste le premier écrasé lorsque le buffer est
+    /// plein, quelle que soit son échéance. Une durée trop grande pour être
+    /// ajoutée à l'instant présent, comme `Duration::MAX`, n'expire jamais.
+    pub fn push_with_ttl(&mut self, item: T, ttl: Duration) -> Option<T> {
+        let now = self.clock.now();
+        self.evict_expired_at(now);
+        let deadline = now.checked_add(ttl).map_or(Deadline::Never, Deadline::At);
+        if self.items.is_empty() {
+            self.sorted = true;
+        } else if self.items.back().is_some_and(|(last, _)| deadline < *last) {
+            self.sorted = false;
+        }
+        self.items.push((deadline, item)).map(|(_, item)| item)
+    }
+
+    /// Retrait du plus ancien élément encore valide
+    pub fn pop(&mut self) -> Option<T> {
+        self.evict_expired();
+        self.items.pop().map(|(_, item)| item)
+    }
+
+    /// Retire du stockage les éléments expirés et retourne leur nombre
+    pub fn evict_expired(&mut self) -> usize {
+        self.evict_expired_at(self.clock.now())
+    }
+
+    /// Retourne le nombre d'éléments encore valides
+    pub fn len(&self) -> usize {
+        self.items.len() - self.expired_at(self.clock.now())
+    }
+
+    /// Vérifie si aucun élément n'est encore valide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parcourt les éléments encore valides, du plus ancien au plus récent
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let now = self.clock.now();
+        self.items
+            .iter()
+            .filter(move |(deadline, _)| !deadline.is_expired(now))
+            .map(|(_, item)| item)
+    }
+
+    /// Vide le buffer
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    // Nombre d'éléments expirés à `now` ; tant que les échéances sont
+    // croissantes, ils sont tous en tête
+    fn expired_at(&self, now: Instant) -> usize {
+        if self.sorted {
+            self.items
+                .partition_point(|(deadline, _)| deadline.is_expired(now))
+        } else {
+            self.items
+                .iter()
+                .filter(|(deadline, _)| deadline.is_expired(now))
+                .count()
+        }
+    }
+
+    fn evict_expired_at(&mut self, now: Instant) -> usize {
+        let expired = self.expired_at(now);
+        if self.sorted {
+            for _ in 0..expired {
+                self.items.pop();
+            }
+        } else if expired > 0 {
+            self.items.retain(|(deadline, _)| !deadline.is_expired(now));
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiration() {
+        let horloge = ManualClock::new();
+        let mut buffer =
+            TimedCircularBuffer::new(Duration::from_secs(60)).with_clock(horloge.clone());
+        buffer.push(1);
+        horloge.advance(Duration::from_secs(30));
+        buffer.push(2);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&1, &2]);
+        horloge.advance(Duration::from_secs(30));
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&2]);
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.items.len(), 2);
+        assert_eq!(buffer.evict_expired(), 1);
+        horloge.advance(Duration::from_secs(30));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop(), None);
+    }
+
+    #[test]
+    fn test_capacite_et_duree() {
+        let horloge = ManualClock::new();
+        let mut buffer = TimedCircularBuffer::with_capacity(2, Duration::from_secs(10))
+            .with_clock(horloge.clone());
+        assert_eq!(buffer.push('a'), None);
+        assert_eq!(buffer.push('b'), None);
+        assert_eq!(buffer.push('c'), Some('a'));
+        horloge.advance(Duration::from_secs(10));
+        assert_eq!(buffer.push('d'), None);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&'d']);
+        assert_eq!(buffer.capacity(), Some(2));
+    }
+
+    #[test]
+    fn test_duree_par_element() {
+        let horloge = ManualClock::new();
+        let mut buffer =
+            TimedCircularBuffer::new(Duration::from_secs(300)).with_clock(horloge.clone());
+        buffer.push("longue");
+        buffer.push_with_ttl("courte", Duration::from_secs(5));
+        buffer.push("autre");
+        horloge.advance(Duration::from_secs(5));
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&"longue", &"autre"]);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.evict_expired(), 1);
+        assert_eq!(buffer.pop(), Some("longue"));
+        horloge.advance(Duration::from_secs(295));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_duree_infinie() {
+        let horloge = ManualClock::new();
+        let mut buffer = TimedCircularBuffer::new(Duration::MAX).with_clock(horloge.clone());
+        buffer.push(1);
+        buffer.push_with_ttl(2, Duration::from_secs(5));
+        buffer.push(3);
+        horloge.advance(Duration::from_secs(3600));
+        assert_eq!(buffer.evict_expired(), 1);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [&1, &3]);
+    }
+}
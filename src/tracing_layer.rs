@@ -0,0 +1,174 @@
+//! Couche `tracing` qui garde les derniers événements en mémoire
+//! (fonctionnalité `tracing`).
+
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tracing_core::field::{Field, Visit};
+use tracing_core::span::{Attributes, Id};
+use tracing_core::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::CircularBuffer;
+
+/// Nature d'un [`TraceRecord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceKind {
+    /// Un événement, par exemple émis par `tracing::info!`.
+    Event,
+    /// L'ouverture d'un span.
+    Span,
+}
+
+/// Événement ou span capturé par une [`RingLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRecord {
+    /// Événement ou span.
+    pub kind: TraceKind,
+    /// Niveau déclaré dans les métadonnées.
+    pub level: Level,
+    /// Cible, le chemin du module par défaut.
+    pub target: &'static str,
+    /// Nom du span, ou nom généré par `tracing` pour un événement.
+    pub name: &'static str,
+    /// Valeur du champ `message`, vide s'il n'y en a pas.
+    pub message: String,
+    /// Autres champs, sous la forme `nom=valeur` séparés par des espaces.
+    pub fields: String,
+    /// Span courant au moment de l'événement, ou à l'ouverture du span.
+    pub span: Option<&'static str>,
+}
+
+/// Couche `tracing_subscriber` qui conserve les `capacity` derniers
+/// événements et spans.
+///
+/// La couche est un handle partagé : on en garde un clone pour relire les
+/// événements, par exemple depuis un gestionnaire de panique.
+#[derive(Clone)]
+pub struct RingLayer {
+    records: Arc<Mutex<CircularBuffer<TraceRecord>>>,
+}
+
+impl RingLayer {
+    /// Création d'une couche gardant `capacity` événements
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(CircularBuffer::new(capacity))),
+        }
+    }
+
+    /// Retire et retourne les événements conservés, du plus ancien au plus
+    /// récent
+    pub fn drain_snapshot(&self) -> Vec<TraceRecord> {
+        self.lock().drain().collect()
+    }
+
+    /// Retourne une copie des événements conservés sans les retirer
+    pub fn snapshot(&self) -> Vec<TraceRecord> {
+        self.lock().iter().cloned().collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CircularBuffer<TraceRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record(
+        &self,
+        kind: TraceKind,
+        metadata: &'static Metadata<'static>,
+        fields: FieldsVisitor,
+        span: Option<&'static str>,
+    ) {
+        self.lock().push(TraceRecord {
+            kind,
+            level: *metadata.level(),
+            target: metadata.target(),
+            name: metadata.name(),
+            message: fields.message,
+            fields: fields.others,
+            span,
+        });
+    }
+}
+
+impl<S> Layer<S> for RingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, ctx: Context<'_, S>) {
+        let mut fields = FieldsVisitor::default();
+        attrs.record(&mut fields);
+        let parent = ctx.current_span().metadata().map(|m| m.name());
+        self.record(TraceKind::Span, attrs.metadata(), fields, parent);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = FieldsVisitor::default();
+        event.record(&mut fields);
+        let span = ctx.event_span(event).map(|span| span.name());
+        self.record(TraceKind::Event, event.metadata(), fields, span);
+    }
+}
+
+// Sépare le champ `message` des autres champs
+#[derive(Default)]
+struct FieldsVisitor {
+    message: String,
+    others: String,
+}
+
+impl Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        // L'écriture dans une `String` n'échoue jamais
+        let _ = if field.name() == "message" {
+            write!(self.message, "{:?}", value)
+        } else {
+            if !self.others.is_empty() {
+                self.others.push(' ');
+            }
+            write!(self.others, "{}={:?}", field.name(), value)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_derniers_evenements() {
+        let couche = RingLayer::new(2);
+        let abonne = tracing_subscriber::registry().with(couche.clone());
+        tracing::subscriber::with_default(abonne, || {
+            tracing::info!("ignoré car écrasé");
+            let _span = tracing::info_span!("requete", id = 7).entered();
+            tracing::warn!(octets = 12, "réponse lente");
+        });
+
+        let evenements = couche.drain_snapshot();
+        assert_eq!(evenements.len(), 2);
+        assert_eq!(evenements[0].kind, TraceKind::Span);
+        assert_eq!(evenements[0].name, "requete");
+        assert_eq!(evenements[0].fields, "id=7");
+        assert_eq!(evenements[1].kind, TraceKind::Event);
+        assert_eq!(evenements[1].level, Level::WARN);
+        assert_eq!(evenements[1].message, "réponse lente");
+        assert_eq!(evenements[1].fields, "octets=12");
+        assert_eq!(evenements[1].span, Some("requete"));
+        assert!(couche.snapshot().is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+//! Seuils de remplissage signalés par [`CircularBuffer::on_watermark`](crate::CircularBuffer::on_watermark).
+
+use alloc::boxed::Box;
+
+/// Seuil franchi par le nombre d'éléments du buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Watermark {
+    /// Le buffer a atteint le seuil haut.
+    High,
+    /// Le buffer est redescendu au seuil bas après avoir atteint le seuil haut.
+    Low,
+}
+
+// Seuils et fermeture appelée à chaque franchissement
+pub(crate) struct Watermarks {
+    high: usize,
+    low: usize,
+    // Vrai entre un franchissement du seuil haut et le retour au seuil bas
+    above: bool,
+    hook: Box<dyn FnMut(Watermark) + Send>,
+}
+
+// SAFETY: la fermeture n'est appelée qu'à travers `&mut self`, comme celle
+// d'éviction.
+unsafe impl Sync for Watermarks {}
+
+impl Watermarks {
+    pub(crate) fn new(high: usize, low: usize, hook: Box<dyn FnMut(Watermark) + Send>) -> Self {
+        assert!(
+            low < high,
+            "Le seuil bas ({}) doit être inférieur au seuil haut ({}).",
+            low,
+            high
+        );
+        Self {
+            high,
+            low,
+            above: false,
+            hook,
+        }
+    }
+
+    // Appelle la fermeture si `len` vient de franchir un seuil
+    pub(crate) fn update(&mut self, len: usize) {
+        if !self.above && len >= self.high {
+            self.above = true;
+            (self.hook)(Watermark::High);
+        } else if self.above && len <= self.low {
+            self.above = false;
+            (self.hook)(Watermark::Low);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CircularBuffer, Watermark};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_franchissements() {
+        let evenements = Arc::new(Mutex::new(Vec::new()));
+        let journal = Arc::clone(&evenements);
+        let mut buffer = CircularBuffer::new(10)
+            .on_watermark(8, 2, move |seuil| journal.lock().unwrap().push(seuil));
+        buffer.extend(0..9);
+        buffer.extend(0..5);
+        for _ in 0..7 {
+            buffer.pop();
+        }
+        assert_eq!(*evenements.lock().unwrap(), [Watermark::High]);
+        buffer.pop();
+        buffer.extend_from_slice(&[1; 8]);
+        buffer.clear();
+        assert_eq!(
+            *evenements.lock().unwrap(),
+            [
+                Watermark::High,
+                Watermark::Low,
+                Watermark::High,
+                Watermark::Low
+            ]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Buffer borné par le poids total de ses éléments plutôt que par leur nombre.
+
+use alloc::vec::Vec;
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+/// Calcule le poids d'un élément, par exemple sa taille en octets.
+///
+/// Toute fermeture `Fn(&T) -> usize` est un `Weigher<T>`.
+pub trait Weigher<T> {
+    /// Retourne le poids de `item`
+    fn weigh(&self, item: &T) -> usize;
+}
+
+impl<T, F: Fn(&T) -> usize> Weigher<T> for F {
+    fn weigh(&self, item: &T) -> usize {
+        self(item)
+    }
+}
+
+/// Pèse un élément à sa longueur en octets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByteLen;
+
+impl<T: AsRef<[u8]>> Weigher<T> for ByteLen {
+    fn weigh(&self, item: &T) -> usize {
+        item.as_ref().len()
+    }
+}
+
+/// Buffer dont la somme des poids ne dépasse jamais `max_weight`.
+///
+/// Un ajout écrase autant d'éléments anciens que nécessaire pour rester sous
+/// la limite. Le poids de chaque élément est calculé une seule fois, à
+/// l'ajout, et gardé à côté de lui.
+pub struct WeightedCircularBuffer<T, W> {
+    // Poids et élément, du plus ancien au plus récent
+    items: CircularBuffer<(usize, T)>,
+    weigher: W,
+    weight: usize,
+    max_weight: usize,
+}
+
+impl<T, W: Weigher<T>> WeightedCircularBuffer<T, W> {
+    /// Création d'un buffer vide dont le poids total reste sous `max_weight`
+    pub fn new(max_weight: usize, weigher: W) -> Self {
+        Self {
+            items: CircularBuffer::with_policy(16, OverflowPolicy::Grow),
+            weigher,
+            weight: 0,
+            max_weight,
+        }
+    }
+
+    /// Ajout d'un élément ; les éléments écrasés pour lui faire de la place
+    /// sont rendus du plus ancien au plus récent
+    ///
+    /// Un élément plus lourd que `max_weight` est refusé et rendu dans `Err`,
+    /// sans rien écraser.
+    pub fn push(&mut self, item: T) -> Result<Vec<T>, T> {
+        let weight = self.weigher.weigh(&item);
+        if weight > self.max_weight {
+            return Err(item);
+        }
+        let mut evicted = Vec::new();
+        while self.weight + weight > self.max_weight {
+            let Some((w, old)) = self.items.pop() else {
+                break;
+            };
+            self.weight -= w;
+            evicted.push(old);
+        }
+        self.items.push((weight, item));
+        self.weight += weight;
+        Ok(evicted)
+    }
+
+    /// Retrait de l'élément le plus ancien
+    pub fn pop(&mut self) -> Option<T> {
+        let (weight, item) = self.items.pop()?;
+        self.weight -= weight;
+        Some(item)
+    }
+
+    /// Retourne une référence à l'élément le plus ancien
+    pub fn peek(&self) -> Option<&T> {
+        self.items.peek().map(|(_, item)| item)
+    }
+
+    /// Parcourt les éléments du plus ancien au plus récent
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().map(|(_, item)| item)
+    }
+
+    /// Retourne le poids total des éléments
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
+    /// Retourne le poids total maximal
+    pub fn max_weight(&self) -> usize {
+        self.max_weight
+    }
+
+    /// Retourne le nombre d'éléments
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Vérifie si le buffer est vide
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Vide le buffer
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.weight = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    #[test]
+    fn test_limite_de_poids() {
+        let mut buffer = WeightedCircularBuffer::new(10, ByteLen);
+        assert_eq!(buffer.push(String::from("abcd")), Ok(Vec::new()));
+        assert_eq!(buffer.push(String::from("efg")), Ok(Vec::new()));
+        assert_eq!(buffer.push(String::from("hij")), Ok(Vec::new()));
+        assert_eq!(buffer.weight(), 10);
+        assert_eq!(
+            buffer.push(String::from("klmno")),
+            Ok(vec![String::from("abcd"), String::from("efg")])
+        );
+        assert_eq!(buffer.weight(), 8);
+        assert_eq!(
+            buffer.push(String::from("trop long !")),
+            Err(String::from("trop long !"))
+        );
+        assert_eq!(buffer.pop().as_deref(), Some("hij"));
+        assert_eq!(buffer.weight(), 5);
+    }
+
+    #[test]
+    fn test_fermeture_comme_peseur() {
+        let mut buffer = WeightedCircularBuffer::new(6, |x: &u32| *x as usize);
+        buffer.push(3).unwrap();
+        buffer.push(2).unwrap();
+        assert_eq!(buffer.push(4), Ok(vec![3]));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [2, 4]);
+    }
+}
@@ -0,0 +1,458 @@
+//! Agrégats sur une fenêtre glissante, mis à jour à chaque ajout.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::{Add, Sub};
+
+use crate::CircularBuffer;
+
+/// Fenêtre des `capacity` derniers éléments et de leur somme.
+///
+/// La somme est corrigée à chaque ajout avec l'élément entrant et l'élément
+/// écrasé, en O(1), sans reparcourir la fenêtre. Pour des flottants, les
+/// erreurs d'arrondi s'accumulent au fil des soustractions ;
+/// [`recompute`](Self::recompute) repart d'une somme exacte.
+#[derive(Debug, Clone)]
+pub struct SlidingSum<T> {
+    window: CircularBuffer<T>,
+    sum: T,
+}
+
+impl<T> SlidingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Création d'une fenêtre vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: CircularBuffer::new(capacity),
+            sum: T::default(),
+        }
+    }
+
+    /// Ajout d'un élément ; l'élément sorti de la fenêtre est rendu
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let evicted = self.window.push(item);
+        self.sum = self.sum + item;
+        if let Some(old) = evicted {
+            self.sum = self.sum - old;
+        }
+        evicted
+    }
+
+    /// Retourne la somme des éléments de la fenêtre
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Recalcule la somme à partir des éléments de la fenêtre
+    pub fn recompute(&mut self) {
+        self.sum = self
+            .window
+            .iter()
+            .fold(T::default(), |sum, &item| sum + item);
+    }
+
+    /// Vide la fenêtre
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.sum = T::default();
+    }
+}
+
+impl<T> SlidingSum<T> {
+    /// Retourne les éléments de la fenêtre
+    pub fn window(&self) -> &CircularBuffer<T> {
+        &self.window
+    }
+
+    /// Retourne le nombre d'éléments de la fenêtre
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Vérifie si la fenêtre est vide
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Vérifie si la fenêtre est pleine
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    /// Retourne la taille de la fenêtre
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+/// Moyenne et variance glissantes des `capacity` derniers échantillons, en
+/// O(1) par ajout.
+#[derive(Debug, Clone)]
+pub struct SlidingMean {
+    sum: SlidingSum<f64>,
+    // Somme des carrés des échantillons de la fenêtre
+    squares: f64,
+}
+
+impl SlidingMean {
+    /// Création d'une fenêtre vide de `capacity` échantillons
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            sum: SlidingSum::new(capacity),
+            squares: 0.0,
+        }
+    }
+
+    /// Ajout d'un échantillon ; l'échantillon sorti de la fenêtre est rendu
+    pub fn push(&mut self, sample: f64) -> Option<f64> {
+        let evicted = self.sum.push(sample);
+        self.squares += sample * sample;
+        if let Some(old) = evicted {
+            self.squares -= old * old;
+        }
+        evicted
+    }
+
+    /// Retourne la variance de la fenêtre, vue comme une population entière,
+    /// ou `None` si elle est vide
+    pub fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let variance = self.squares / self.len() as f64 - mean * mean;
+        // Les arrondis peuvent rendre une variance nulle très légèrement négative
+        Some(variance.max(0.0))
+    }
+
+    /// Retourne l'écart type de la fenêtre, ou `None` si elle est vide
+    #[cfg(feature = "std")]
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Recalcule la somme et la somme des carrés à partir de la fenêtre
+    pub fn recompute(&mut self) {
+        self.sum.recompute();
+        self.squares = self.window().iter().map(|x| x * x).sum();
+    }
+
+    /// Retourne la moyenne de la fenêtre, ou `None` si elle est vide
+    pub fn mean(&self) -> Option<f64> {
+        if self.sum.is_empty() {
+            None
+        } else {
+            Some(self.sum.sum() / self.sum.len() as f64)
+        }
+    }
+
+    /// Retourne les échantillons de la fenêtre
+    pub fn window(&self) -> &CircularBuffer<f64> {
+        self.sum.window()
+    }
+
+    /// Retourne le nombre d'échantillons de la fenêtre
+    pub fn len(&self) -> usize {
+        self.sum.len()
+    }
+
+    /// Vérifie si la fenêtre est vide
+    pub fn is_empty(&self) -> bool {
+        self.sum.is_empty()
+    }
+
+    /// Vide la fenêtre
+    pub fn clear(&mut self) {
+        self.sum.clear();
+        self.squares = 0.0;
+    }
+}
+
+/// Moyenne mobile exponentielle : chaque échantillon compte pour `alpha` et
+/// l'historique pour `1 - alpha`.
+///
+/// Elle ne garde aucune fenêtre et accompagne par exemple une
+/// [`SlidingMean`] alimentée par les mêmes échantillons.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ewma {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ewma {
+    /// Création d'une moyenne de facteur de lissage `alpha`
+    ///
+    /// # Panics
+    ///
+    /// Panique si `alpha` n'est pas dans `]0, 1]`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "Le facteur de lissage doit être dans ]0, 1] (reçu {}).",
+            alpha
+        );
+        Self { alpha, value: None }
+    }
+
+    /// Ajoute un échantillon et retourne la nouvelle moyenne ; le premier
+    /// échantillon sert de valeur initiale
+    pub fn push(&mut self, sample: f64) -> f64 {
+        let value = match self.value {
+            Some(value) => value + self.alpha * (sample - value),
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    /// Retourne la moyenne courante, ou `None` avant le premier échantillon
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Retourne le facteur de lissage
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Oublie les échantillons reçus
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// Minimum et maximum des `capacity` derniers éléments, en O(1) amorti par
+/// ajout.
+///
+/// Deux files monotones gardent, avec leur numéro de séquence, les seuls
+/// éléments pouvant encore devenir le minimum ou le maximum de la fenêtre.
+#[derive(Debug, Clone)]
+pub struct SlidingMinMax<T> {
+    window: CircularBuffer<T>,
+    // Valeurs croissantes (minimum en tête) et décroissantes (maximum en tête)
+    minima: CircularBuffer<(u64, T)>,
+    maxima: CircularBuffer<(u64, T)>,
+}
+
+impl<T: Ord + Clone> SlidingMinMax<T> {
+    /// Création d'une fenêtre vide de `capacity` éléments
+    ///
+    /// # Panics
+    ///
+    /// Panique si `capacity` vaut 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: CircularBuffer::new(capacity),
+            minima: CircularBuffer::new(capacity),
+            maxima: CircularBuffer::new(capacity),
+        }
+    }
+
+    /// Ajout d'un élément ; l'élément sorti de la fenêtre est rendu
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let evicted = self.window.push(item.clone());
+        let seq = self.window.head.wrapping_sub(1);
+        // Oublier les candidats sortis de la fenêtre
+        for queue in [&mut self.minima, &mut self.maxima] {
+            while queue
+                .front()
+                .is_some_and(|(s, _)| (s.wrapping_sub(self.window.tail) as i64) < 0)
+            {
+                queue.pop();
+            }
+        }
+        while self.minima.back().is_some_and(|(_, v)| *v >= item) {
+            self.minima.pop_back();
+        }
+        while self.maxima.back().is_some_and(|(_, v)| *v <= item) {
+            self.maxima.pop_back();
+        }
+        self.minima.push((seq, item.clone()));
+        self.maxima.push((seq, item));
+        evicted
+    }
+
+    /// Retourne le plus petit élément de la fenêtre
+    pub fn min(&self) -> Option<&T> {
+        self.minima.front().map(|(_, v)| v)
+    }
+
+    /// Retourne le plus grand élément de la fenêtre
+    pub fn max(&self) -> Option<&T> {
+        self.maxima.front().map(|(_, v)| v)
+    }
+
+    /// Vide la fenêtre
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.minima.clear();
+        self.maxima.clear();
+    }
+}
+
+impl<T> SlidingMinMax<T> {
+    /// Retourne les éléments de la fenêtre
+    pub fn window(&self) -> &CircularBuffer<T> {
+        &self.window
+    }
+
+    /// Retourne le nombre d'éléments de la fenêtre
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Vérifie si la fenêtre est vide
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Retourne la taille de la fenêtre
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl<T: Copy + PartialOrd> CircularBuffer<T> {
+    /// Retourne le quantile `q` des éléments présents, par la méthode du rang
+    /// le plus proche : `quantile(0.5)` est la médiane, `quantile(0.99)` le
+    /// 99e centile, ou `None` si le buffer est vide
+    ///
+    /// Le calcul est exact : les éléments sont copiés puis le rang cherché
+    /// est sélectionné en temps linéaire, sans trier toute la copie.
+    ///
+    /// # Panics
+    ///
+    /// Panique si `q` n'est pas compris entre 0 et 1.
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        let mut scratch: Vec<T> = self.iter().copied().collect();
+        let index = quantile_index(q, scratch.len())?;
+        Some(*scratch.select_nth_unstable_by(index, compare).1)
+    }
+
+    /// Retourne plusieurs quantiles à partir d'une seule copie triée, par
+    /// exemple `quantiles(&[0.5, 0.95, 0.99])`
+    ///
+    /// # Panics
+    ///
+    /// Panique si l'un des quantiles n'est pas compris entre 0 et 1.
+    pub fn quantiles(&self, qs: &[f64]) -> Option<Vec<T>> {
+        let mut scratch: Vec<T> = self.iter().copied().collect();
+        scratch.sort_unstable_by(compare);
+        qs.iter()
+            .map(|&q| quantile_index(q, scratch.len()).map(|index| scratch[index]))
+            .collect()
+    }
+}
+
+// Les valeurs incomparables, comme `NaN`, sont considérées égales
+fn compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+// Indice du rang le plus proche parmi `len` valeurs triées
+fn quantile_index(q: f64, len: usize) -> Option<usize> {
+    assert!(
+        (0.0..=1.0).contains(&q),
+        "Le quantile doit être compris entre 0 et 1 (reçu {}).",
+        q
+    );
+    if len == 0 {
+        return None;
+    }
+    // Arrondi supérieur, sans `f64::ceil` absent de `core`
+    let exact = q * len as f64;
+    let mut rank = exact as usize;
+    if (rank as f64) < exact {
+        rank += 1;
+    }
+    Some(rank.clamp(1, len) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_somme_glissante() {
+        let mut somme = SlidingSum::new(3);
+        for x in 1..=5 {
+            somme.push(x);
+        }
+        assert_eq!(somme.sum(), 3 + 4 + 5);
+        assert_eq!(somme.window(), &[3, 4, 5]);
+        somme.recompute();
+        assert_eq!(somme.sum(), 12);
+        somme.clear();
+        assert_eq!(somme.sum(), 0);
+    }
+
+    #[test]
+    fn test_moyenne_glissante() {
+        let mut moyenne = SlidingMean::new(4);
+        assert_eq!(moyenne.mean(), None);
+        for x in [2.0, 4.0, 6.0, 8.0, 10.0] {
+            moyenne.push(x);
+        }
+        assert_eq!(moyenne.mean(), Some(7.0));
+        assert_eq!(moyenne.variance(), Some(5.0));
+        assert_eq!(moyenne.stddev(), Some(5.0f64.sqrt()));
+        moyenne.recompute();
+        assert_eq!(moyenne.variance(), Some(5.0));
+    }
+
+    #[test]
+    fn test_moyenne_exponentielle() {
+        let mut ewma = Ewma::new(0.5);
+        assert_eq!(ewma.value(), None);
+        assert_eq!(ewma.push(10.0), 10.0);
+        assert_eq!(ewma.push(20.0), 15.0);
+        assert_eq!(ewma.push(15.0), 15.0);
+        ewma.reset();
+        assert_eq!(ewma.push(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let mut latences = CircularBuffer::new(100);
+        latences.extend((1..=150).rev());
+        assert_eq!(latences.quantile(0.5), Some(50));
+        assert_eq!(latences.quantile(0.0), Some(1));
+        assert_eq!(latences.quantile(1.0), Some(100));
+        assert_eq!(
+            latences.quantiles(&[0.5, 0.95, 0.99]),
+            Some(vec![50, 95, 99])
+        );
+        assert_eq!(CircularBuffer::<f64>::new(2).quantile(0.5), None);
+    }
+
+    #[test]
+    fn test_min_max_glissants() {
+        let mut fenetre = SlidingMinMax::new(3);
+        assert_eq!(fenetre.min(), None);
+        let mut observes = Vec::new();
+        for x in [5, 1, 4, 3, 8, 2, 2, 9] {
+            fenetre.push(x);
+            observes.push((*fenetre.min().unwrap(), *fenetre.max().unwrap()));
+        }
+        assert_eq!(
+            observes,
+            [
+                (5, 5),
+                (1, 5),
+                (1, 5),
+                (1, 4),
+                (3, 8),
+                (2, 8),
+                (2, 8),
+                (2, 9)
+            ]
+        );
+    }
+}